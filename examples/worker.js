@@ -0,0 +1,28 @@
+// Runs inside a module Web Worker started by `YakeWorker` in yake-worker.js.
+// Build the package with `wasm-pack build --target web` first.
+import init, { Yake } from '../pkg/yake_wasm.js';
+
+const ready = init();
+const jobs = new Map();
+
+self.onmessage = async ({ data }) => {
+    if (data.type === 'cancel') {
+        jobs.get(data.id)?.abort();
+        return;
+    }
+
+    const controller = new AbortController();
+    jobs.set(data.id, controller);
+    let yake;
+    try {
+        await ready;
+        yake = data.options ? Yake.with_options(data.options) : new Yake();
+        const results = await yake.get_n_best_async(data.text, data.n, controller.signal, data.chunkSize);
+        self.postMessage({ id: data.id, results });
+    } catch (error) {
+        self.postMessage({ id: data.id, error: { name: error.name, message: error.message } });
+    } finally {
+        yake?.free();
+        jobs.delete(data.id);
+    }
+};
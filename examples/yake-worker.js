@@ -0,0 +1,56 @@
+// Extracts keywords in a Web Worker, so long texts never block the page.
+//
+//   const yake = new YakeWorker();
+//   const controller = new AbortController();
+//   const keywords = await yake.extract(text, 10, { options: { ngram: 2 }, signal: controller.signal });
+export class YakeWorker {
+    constructor(url = new URL('./worker.js', import.meta.url)) {
+        this.worker = new Worker(url, { type: 'module' });
+        this.pending = new Map();
+        this.nextId = 0;
+        this.worker.onmessage = ({ data }) => {
+            const job = this.pending.get(data.id);
+            if (!job) {
+                return;
+            }
+            this.pending.delete(data.id);
+            if (data.error) {
+                job.reject(Object.assign(new Error(data.error.message), { name: data.error.name }));
+            } else {
+                job.resolve(data.results);
+            }
+        };
+    }
+
+    /**
+     * Resolves to the `n` best keywords of `text`. `options` are those of
+     * `Yake.with_options`, `chunkSize` that of `get_n_best_async`. Aborting
+     * `signal` rejects right away with its reason and stops the worker's job.
+     */
+    extract(text, n, { options, signal, chunkSize } = {}) {
+        const id = this.nextId++;
+        return new Promise((resolve, reject) => {
+            if (signal?.aborted) {
+                reject(signal.reason);
+                return;
+            }
+            this.pending.set(id, { resolve, reject });
+            signal?.addEventListener('abort', () => {
+                if (this.pending.delete(id)) {
+                    this.worker.postMessage({ type: 'cancel', id });
+                    reject(signal.reason);
+                }
+            }, { once: true });
+            this.worker.postMessage({ type: 'extract', id, text, n, options, chunkSize });
+        });
+    }
+
+    /** Stops the worker, rejecting every pending extraction. */
+    terminate() {
+        this.worker.terminate();
+        for (const job of this.pending.values()) {
+            job.reject(new Error('the keyword worker was terminated'));
+        }
+        this.pending.clear();
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::{HashMap, HashSet};
+
+/// Finds acronyms defined in a tokenized sentence, either as
+/// "Long Form (LF)" or as "LF (Long Form)".
+///
+/// Returns each lowercase acronym with the surface form of its expansion.
+pub(crate) fn detect(words: &[String], stopwords: &HashSet<String>) -> HashMap<String, String> {
+    let mut acronyms = HashMap::<String, String>::new();
+    for (open, _) in words.iter().enumerate().filter(|(_, w)| *w == "(") {
+        let close = match words[open..].iter().position(|w| w == ")") {
+            Some(close) => open + close,
+            None => continue,
+        };
+        let inner = &words[open + 1..close];
+
+        if inner.len() == 1 && is_acronym(&inner[0]) {
+            let letters = letters(&inner[0]);
+            if let Some(start) = expansion_start(&words[..open], &letters, stopwords) {
+                acronyms.insert(inner[0].to_lowercase(), words[start..open].join(" "));
+            }
+        } else if open > 0 && is_acronym(&words[open - 1]) {
+            let letters = letters(&words[open - 1]);
+            if expansion_start(inner, &letters, stopwords) == Some(0) {
+                acronyms.insert(words[open - 1].to_lowercase(), inner.join(" "));
+            }
+        }
+    }
+    acronyms
+}
+
+fn is_acronym(word: &str) -> bool {
+    let length = word.chars().count();
+    (2..=10).contains(&length)
+        && word.chars().all(|c| c.is_alphanumeric())
+        && word.chars().filter(|c| c.is_alphabetic()).all(|c| c.is_uppercase())
+        && word.chars().filter(|c| c.is_uppercase()).count() >= 2
+}
+
+fn letters(acronym: &str) -> Vec<char> {
+    acronym.chars().filter(|c| c.is_alphabetic()).flat_map(|c| c.to_lowercase()).collect()
+}
+
+/// Walks back from the end of `words` matching each word's initial against
+/// the acronym's letters, skipping stopwords that don't match ("Bank of
+/// America"). Returns where the expansion starts if every letter matched.
+fn expansion_start(words: &[String], letters: &[char], stopwords: &HashSet<String>) -> Option<usize> {
+    let mut remaining = letters.len();
+    let mut start = words.len();
+    while remaining > 0 && start > 0 {
+        let word = words[start - 1].to_lowercase();
+        let initial = word.chars().next().filter(|c| c.is_alphanumeric())?;
+        if initial == letters[remaining - 1] {
+            remaining -= 1;
+        } else if !stopwords.contains(&word) {
+            return None;
+        }
+        start -= 1;
+    }
+    if remaining == 0 {
+        Some(start)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,126 @@
+use js_sys::{Error, Function, Promise};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::{future_to_promise, JsFuture};
+use web_sys::AbortSignal;
+
+use crate::scorer::ScorerKind;
+use crate::{classic, segtok, Features, Mode, Prepared, Results, TermDictionary, Weights, Yake};
+
+/// Sentences, terms or candidates handled between two yields when no
+/// `chunk_size` is given.
+const CHUNK_SIZE: usize = 100;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &Function, timeout: i32);
+}
+
+#[wasm_bindgen]
+impl Yake {
+    /// Like `get_n_best`, but returns a Promise and yields to the event loop
+    /// every `chunk_size` sentences (100 by default) while tokenizing, and
+    /// every `chunk_size` terms or candidates while weighing them, so long
+    /// texts don't freeze the page. Classic mode yields the same way.
+    ///
+    /// Aborting `signal` stops the extraction at the next yield and rejects
+    /// the Promise with the signal's reason, an `AbortError` by default.
+    #[wasm_bindgen(unchecked_return_type = "Promise<ResultItem[]>")]
+    pub fn get_n_best_async(&self, text: String, n: Option<usize>, signal: Option<AbortSignal>, chunk_size: Option<usize>) -> Promise {
+        let mut yake = self.clone();
+        let chunk_size = chunk_size.unwrap_or(CHUNK_SIZE).max(1);
+        future_to_promise(async move {
+            let signal = signal.as_ref();
+            let ranked = match yake.config.mode {
+                Mode::Classic => yake.rank_classic_async(&text, signal, chunk_size).await?,
+                Mode::Enhanced => yake.rank_enhanced_async(text, signal, chunk_size).await?,
+            };
+            pause(signal).await?;
+            Ok(serde_wasm_bindgen::to_value(&yake.select(ranked, n))?)
+        })
+    }
+}
+
+impl Yake {
+    /// `rank_enhanced`, pausing between chunks of sentences, terms and
+    /// candidates. Scorers other than YAKE weigh all candidates at once.
+    async fn rank_enhanced_async(&mut self, text: String, signal: Option<&AbortSignal>, chunk_size: usize) -> Result<Results, JsValue> {
+        let mut terms = TermDictionary::new();
+        let mut sentences = Vec::new();
+        for chunk in self.split_sentences(text).chunks(chunk_size) {
+            pause(signal).await?;
+            sentences.extend(chunk.iter().map(|s| self.build_sentence(s, &mut terms)));
+        }
+
+        pause(signal).await?;
+        let prepared = self.prepare(sentences, terms);
+        if prepared.words.is_empty() {
+            return Ok(Results::new());
+        }
+        let weights = match self.config.scorer {
+            ScorerKind::Yake => {
+                let Prepared { sentences, terms, words, contexts, candidates, dedupe_subgrams, .. } = &prepared;
+                let stats = self.tf_stats(words, terms);
+                let mut features = Features::new();
+                for chunk in words.iter().collect::<Vec<_>>().chunks(chunk_size) {
+                    pause(signal).await?;
+                    features.extend(chunk.iter().map(|(id, word)| (**id, self.term_features(**id, word, &stats, contexts, sentences, terms))));
+                }
+                let bigrams = Yake::bigrams(sentences);
+                let mut weights = Weights::new();
+                for chunk in candidates.iter().collect::<Vec<_>>().chunks(chunk_size) {
+                    pause(signal).await?;
+                    weights.extend(chunk.iter().map(|(k, v)| (k.to_string(), self.candidate_weight(k, v, &features, &bigrams, dedupe_subgrams))));
+                }
+                weights
+            }
+            _ => {
+                pause(signal).await?;
+                self.weigh(&prepared)
+            }
+        };
+        pause(signal).await?;
+        Ok(self.order(&prepared, weights))
+    }
+
+    /// `classic::rank`, pausing between chunks of sentences and candidates.
+    async fn rank_classic_async(&self, text: &str, signal: Option<&AbortSignal>, chunk_size: usize) -> Result<Results, JsValue> {
+        pause(signal).await?;
+        let sentences = segtok::sentences(text);
+        let mut document = classic::Document::default();
+        for (start, chunk) in (0..).step_by(chunk_size).zip(sentences.chunks(chunk_size)) {
+            pause(signal).await?;
+            for (i, sentence) in chunk.iter().enumerate() {
+                document.read(start + i, sentence, &self.config);
+            }
+        }
+
+        document.weigh_terms(sentences.len());
+        let total = document.candidates.len();
+        let mut weights = Vec::with_capacity(total);
+        for start in (0..total).step_by(chunk_size) {
+            pause(signal).await?;
+            weights.extend(document.weigh_candidates(start..total.min(start + chunk_size)));
+        }
+        Ok(document.rank(weights))
+    }
+}
+
+/// Lets the event loop run, then fails if `signal` was aborted meanwhile.
+async fn pause(signal: Option<&AbortSignal>) -> Result<(), JsValue> {
+    JsFuture::from(Promise::new(&mut |resolve, _| set_timeout(&resolve, 0))).await?;
+    match signal {
+        Some(signal) if signal.aborted() => Err(abort_reason(signal)),
+        _ => Ok(()),
+    }
+}
+
+fn abort_reason(signal: &AbortSignal) -> JsValue {
+    let reason = signal.reason();
+    if !reason.is_undefined() {
+        return reason;
+    }
+    let error = Error::new("keyword extraction was aborted");
+    error.set_name("AbortError");
+    error.into()
+}
@@ -0,0 +1,64 @@
+//! Command line front end: extracts keywords from a file or evaluates the
+//! extractor against a dataset with gold keyphrases.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use yake_wasm::evaluation;
+use yake_wasm::Yake;
+
+const USAGE: &str = "usage:
+  yake extract <file> [-n <count>] [--options <json>]
+  yake eval <dataset> [--options <json>] [--json]
+
+<dataset> is a JSONL file of {\"text\", \"keywords\"} objects, or a directory of
+documents with a <name>.key file next to each one or in a sibling keys directory.";
+
+fn main() {
+    if let Err(message) = run(env::args().skip(1).collect()) {
+        eprintln!("{}\n\n{}", message, USAGE);
+        process::exit(2);
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut positional = Vec::<String>::new();
+    let mut options = String::from("{}");
+    let mut n = None;
+    let mut json = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" => n = Some(args.next().and_then(|v| v.parse::<usize>().ok()).ok_or("-n expects a number")?),
+            "--options" => options = args.next().ok_or("--options expects a JSON object")?,
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut yake = Yake::from_json(&options).map_err(|e| format!("invalid options: {}", e))?;
+    match positional.iter().map(|a| a.as_str()).collect::<Vec<&str>>()[..] {
+        ["extract", file] => {
+            let text = fs::read_to_string(file).map_err(|e| format!("{}: {}", file, e))?;
+            let results = yake.extract(text, n);
+            println!("{}", serde_json::to_string_pretty(&results).map_err(|e| e.to_string())?);
+        }
+        ["eval", dataset] => {
+            let samples = evaluation::load_dataset(Path::new(dataset)).map_err(|e| format!("{}: {}", dataset, e))?;
+            let report = evaluation::evaluate(&mut yake, &samples);
+            if json {
+                println!("{}", serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?);
+            } else {
+                print!("{}", report);
+            }
+        }
+        _ => return Err(String::from("expected a subcommand")),
+    }
+    Ok(())
+}
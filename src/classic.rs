@@ -0,0 +1,268 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use stats::{mean, median, stddev};
+
+use crate::levenshtein::Levenshtein;
+use crate::segtok;
+use crate::{compare_scores, Config, ResultItem, Results};
+
+/// Co-occurrence window used by the reference implementation.
+const WINDOW_SIZE: usize = 1;
+/// Similarity above which the reference implementation drops a keyword as a
+/// near duplicate of a better one.
+pub(crate) const DEDUPE_LIM: f64 = 0.9;
+
+/// The reference YAKE algorithm (Campos et al., 2020), without the
+/// enhancements of the default pipeline.
+///
+/// `text` is split into words with the reference's tokenizer, see `segtok`.
+/// Words are tagged and grouped into terms the way the reference does:
+/// lowercase, with a trailing plural "s" dropped, and co-occurrences and
+/// candidates never cross punctuation. Punctuation is ASCII only, like the
+/// reference's `string.punctuation`, whatever `config.punctuation` says.
+/// Candidates are scored without a subgram penalty and are returned best
+/// first, before deduplication.
+pub(crate) fn rank(text: &str, config: &Config) -> Results {
+    let sentences = segtok::sentences(text);
+    let mut document = Document::default();
+    for (sentence_id, sentence) in sentences.iter().enumerate() {
+        document.read(sentence_id, sentence, config);
+    }
+    document.weigh_terms(sentences.len());
+    let weights = document.weigh_candidates(0..document.candidates.len());
+    document.rank(weights)
+}
+
+/// Whether `candidate` is too similar to any keyword already selected, using
+/// the reference's `seqm` similarity.
+pub(crate) fn is_redundant(candidate: &str, previous: &[String]) -> bool {
+    previous.iter().any(|p| Levenshtein::ratio(candidate.to_owned(), p.to_owned()) > DEDUPE_LIM)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tag {
+    Digit,
+    Unparsable,
+    Acronym,
+    Noun,
+    Plain,
+}
+impl Tag {
+    fn of(word: &str, position: usize) -> Tag {
+        if word.replace(',', "").parse::<f64>().is_ok() {
+            return Tag::Digit;
+        }
+        let digits = word.chars().filter(|c| c.is_numeric()).count();
+        let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+        if (digits > 0) == (letters > 0) || word.chars().filter(|c| c.is_ascii_punctuation()).count() > 1 {
+            Tag::Unparsable
+        } else if word.chars().all(|c| c.is_uppercase()) {
+            Tag::Acronym
+        } else if position != 0 && word.chars().next().is_some_and(|c| c.is_uppercase()) {
+            Tag::Noun
+        } else {
+            Tag::Plain
+        }
+    }
+
+    fn is_parsable(self) -> bool {
+        self != Tag::Digit && self != Tag::Unparsable
+    }
+}
+
+#[derive(Debug, Default)]
+struct Term {
+    stopword: bool,
+    tf: f64,
+    tf_a: f64,
+    tf_n: f64,
+    sentence_ids: Vec<usize>,
+    /// Distinct terms seen right before this one, and how often.
+    left: HashMap<usize, f64>,
+    right: HashMap<usize, f64>,
+    weight: f64,
+}
+impl Term {
+    fn occur(&mut self, tag: Tag, sentence_id: usize) {
+        self.tf += 1.0;
+        match tag {
+            Tag::Acronym => self.tf_a += 1.0,
+            Tag::Noun => self.tf_n += 1.0,
+            _ => {}
+        }
+        if self.sentence_ids.last() != Some(&sentence_id) {
+            self.sentence_ids.push(sentence_id);
+        }
+    }
+
+    fn weigh(&mut self, max_tf: f64, mean_tf: f64, std_tf: f64, sentences: usize) {
+        let left_total = self.left.values().sum::<f64>();
+        let right_total = self.right.values().sum::<f64>();
+        let pl = if left_total > 0.0 { self.left.len() as f64 / left_total } else { 0.0 };
+        let pr = if right_total > 0.0 { self.right.len() as f64 / right_total } else { 0.0 };
+
+        let relatedness = (0.5 + pl * (self.tf / max_tf)) + (0.5 + pr * (self.tf / max_tf));
+        let frequency = self.tf / (mean_tf + std_tf);
+        let different = self.sentence_ids.len() as f64 / sentences as f64;
+        let casing = self.tf_a.max(self.tf_n) / (1.0 + self.tf.ln());
+        let position = (3.0 + median(self.sentence_ids.iter().map(|s| *s as f64)).unwrap()).ln().ln();
+
+        self.weight = (position * relatedness) / (casing + ((frequency + different) / relatedness));
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Candidate {
+    raw: String,
+    keyword: String,
+    terms: Vec<usize>,
+    tf: f64,
+    /// Whether some occurrence contains no digit or unparsable word.
+    parsable: bool,
+}
+impl Candidate {
+    fn is_valid(&self, terms: &[Term]) -> bool {
+        self.parsable && !terms[self.terms[0]].stopword && !terms[*self.terms.last().unwrap()].stopword
+    }
+
+    /// Stopwords inside the candidate are weighed by how strongly they bind
+    /// their neighbours, the reference's "bi" stopword weighting.
+    fn weight(&self, document: &Document) -> f64 {
+        let mut sum = 0.0;
+        let mut prod = 1.0;
+        for (t, &id) in self.terms.iter().enumerate() {
+            let term = &document.terms[id];
+            if !term.stopword {
+                sum += term.weight;
+                prod *= term.weight;
+                continue;
+            }
+            let mut prob_t1 = 0.0;
+            if t > 0 {
+                let left = self.terms[t - 1];
+                prob_t1 = term.left.get(&left).copied().unwrap_or(0.0) / document.terms[left].tf;
+            }
+            let mut prob_t2 = 0.0;
+            if t + 1 < self.terms.len() {
+                let right = self.terms[t + 1];
+                prob_t2 = term.right.get(&right).copied().unwrap_or(0.0) / document.terms[right].tf;
+            }
+            let prob = prob_t1 * prob_t2;
+            prod *= 1.0 + (1.0 - prob);
+            sum -= 1.0 - prob;
+        }
+        prod / ((sum + 1.0) * self.tf)
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Document {
+    ids: HashMap<String, usize>,
+    terms: Vec<Term>,
+    keys: HashMap<String, usize>,
+    /// In order of first occurrence, so equal scores keep that order.
+    pub candidates: Vec<Candidate>,
+}
+impl Document {
+    /// Adds the terms, co-occurrences and candidates of one sentence.
+    pub(crate) fn read(&mut self, sentence_id: usize, sentence: &[String], config: &Config) {
+        let mut block = Vec::<(Tag, &str, usize)>::new();
+        for (position, word) in sentence.iter().enumerate() {
+            if word.chars().all(|c| c.is_ascii_punctuation()) {
+                block.clear();
+                continue;
+            }
+
+            let tag = Tag::of(word, position);
+            let term = self.term(word, &config.stopwords);
+            self.terms[term].occur(tag, sentence_id);
+
+            if tag.is_parsable() {
+                for &(previous_tag, _, previous) in &block[block.len().saturating_sub(WINDOW_SIZE)..] {
+                    if previous_tag.is_parsable() {
+                        self.cooccur(previous, term);
+                    }
+                }
+            }
+
+            let start = block.len().saturating_sub(config.ngram.saturating_sub(1));
+            for from in (start..=block.len()).rev() {
+                let mut words = block[from..].to_vec();
+                words.push((tag, word, term));
+                self.candidate(&words);
+            }
+            block.push((tag, word, term));
+        }
+    }
+
+    /// The weights of the candidates in `range`, `None` for those that can't
+    /// be keywords. Terms must be weighed first.
+    pub(crate) fn weigh_candidates(&self, range: Range<usize>) -> Vec<Option<f64>> {
+        self.candidates[range].iter().map(|c| c.is_valid(&self.terms).then(|| c.weight(self))).collect()
+    }
+
+    /// The candidates best first, given the weights of all of them.
+    pub(crate) fn rank(&self, weights: Vec<Option<f64>>) -> Results {
+        let mut ranked = self.candidates.iter().zip(weights).filter_map(|(c, h)| Some((c, h?))).collect::<Vec<(&Candidate, f64)>>();
+        ranked.sort_by(|a, b| compare_scores(a.1, b.1));
+        ranked.into_iter().map(|(c, h)| ResultItem::new(c.raw.to_owned(), c.keyword.to_owned(), h)).collect()
+    }
+
+    fn term(&mut self, word: &str, stopwords: &HashSet<String>) -> usize {
+        let lowercase = word.to_lowercase();
+        let mut unique = lowercase.to_owned();
+        if unique.ends_with('s') && unique.chars().count() > 3 {
+            unique.pop();
+        }
+        if let Some(id) = self.ids.get(&unique) {
+            return *id;
+        }
+
+        let simple = unique.chars().filter(|c| !c.is_ascii_punctuation()).count();
+        let stopword = stopwords.contains(&lowercase) || stopwords.contains(&unique) || simple < 3;
+        self.ids.insert(unique, self.terms.len());
+        self.terms.push(Term { stopword, ..Term::default() });
+        self.terms.len() - 1
+    }
+
+    fn cooccur(&mut self, left: usize, right: usize) {
+        *self.terms[right].left.entry(left).or_insert(0.0) += 1.0;
+        *self.terms[left].right.entry(right).or_insert(0.0) += 1.0;
+    }
+
+    fn candidate(&mut self, words: &[(Tag, &str, usize)]) {
+        let keyword = words.iter().map(|w| w.1.to_lowercase()).collect::<Vec<String>>().join(" ");
+        let parsable = words.iter().all(|w| w.0.is_parsable());
+        match self.keys.get(&keyword) {
+            Some(&index) => {
+                let candidate = &mut self.candidates[index];
+                candidate.tf += 1.0;
+                candidate.parsable |= parsable;
+            }
+            None => {
+                self.keys.insert(keyword.to_owned(), self.candidates.len());
+                self.candidates.push(Candidate {
+                    raw: words.iter().map(|w| w.1).collect::<Vec<&str>>().join(" "),
+                    keyword,
+                    terms: words.iter().map(|w| w.2).collect(),
+                    tf: 1.0,
+                    parsable,
+                });
+            }
+        }
+    }
+
+    pub(crate) fn weigh_terms(&mut self, sentences: usize) {
+        let valid_tf = self.terms.iter().filter(|t| !t.stopword).map(|t| t.tf).collect::<Vec<f64>>();
+        if valid_tf.is_empty() {
+            return;
+        }
+        let mean_tf = mean(valid_tf.iter().copied());
+        let std_tf = stddev(valid_tf.iter().copied());
+        let max_tf = self.terms.iter().map(|t| t.tf).fold(0.0, f64::max);
+        for term in &mut self.terms {
+            term.weigh(max_tf, mean_tf, std_tf, sentences);
+        }
+    }
+}
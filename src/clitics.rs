@@ -0,0 +1,49 @@
+use serde::{de, Deserialize, Deserializer};
+
+/// Languages with clitic rules.
+const LANGUAGES: [&str; 3] = ["en", "fr", "it"];
+
+/// Characters accepted as apostrophes: ASCII, right and left single
+/// quotation marks, modifier letter apostrophe and prime.
+const APOSTROPHES: [char; 5] = ['\'', '\u{2019}', '\u{2018}', '\u{02BC}', '\u{2032}'];
+
+/// Elided articles, prepositions and pronouns written before a word.
+const FRENCH: [&str; 13] = ["l'", "d'", "j'", "m'", "n'", "s'", "t'", "c'", "qu'", "jusqu'", "lorsqu'", "puisqu'", "quoiqu'"];
+const ITALIAN: [&str; 21] = [
+    "l'", "d'", "c'", "m'", "t'", "s'", "v'", "un'", "all'", "dall'", "dell'", "nell'", "sull'", "coll'", "agl'", "dagl'",
+    "degl'", "negl'", "sugl'", "quest'", "quell'",
+];
+
+/// Deserializes a language code, rejecting those without clitic rules
+/// rather than silently stripping nothing.
+pub(crate) fn language<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let language = String::deserialize(deserializer)?;
+    if LANGUAGES.contains(&language.as_str()) {
+        Ok(language)
+    } else {
+        Err(de::Error::unknown_variant(&language, &LANGUAGES))
+    }
+}
+
+/// Strips the clitics `language` attaches to `word`: the English possessive
+/// "'s" at the end, or French and Italian elisions such as "l'" and "dell'"
+/// at the start. Any apostrophe in `APOSTROPHES` matches.
+pub(crate) fn strip(word: &str, language: &str) -> String {
+    let folded = word.to_lowercase().replace(APOSTROPHES, "'");
+    let prefixes: &[&str] = match language {
+        "fr" => &FRENCH,
+        "it" => &ITALIAN,
+        _ => &[],
+    };
+    if language == "en" && folded.ends_with("'s") && folded.chars().count() > 2 {
+        let keep = word.chars().count() - 2;
+        return word.chars().take(keep).collect();
+    }
+    if let Some(prefix) = prefixes.iter().filter(|p| folded.starts_with(*p)).max_by_key(|p| p.len()) {
+        let skip = prefix.chars().count();
+        if word.chars().count() > skip {
+            return word.chars().skip(skip).collect();
+        }
+    }
+    word.to_string()
+}
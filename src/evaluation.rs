@@ -0,0 +1,196 @@
+//! Scores extracted keywords against gold-standard keyphrases.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::preprocessor::Preprocessor;
+use crate::Yake;
+
+/// Cut-offs reported as precision, recall and F1 at k.
+pub const CUTOFFS: [usize; 3] = [5, 10, 15];
+
+/// A document and its gold keyphrases.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Sample {
+    #[serde(alias = "document", alias = "abstract", deserialize_with = "text_or_tokens")]
+    pub text: String,
+    #[serde(alias = "keyphrases", alias = "keys")]
+    pub keywords: Vec<String>,
+}
+
+/// Loads a dataset from either a JSONL file with one `{"text", "keywords"}`
+/// object per line, or a directory of documents with a `<name>.key` gold list
+/// next to each one or in a sibling `keys` directory.
+///
+/// `.key` files hold one keyphrase per line; `;` also separates keyphrases.
+pub fn load_dataset(path: &Path) -> io::Result<Vec<Sample>> {
+    if path.is_dir() {
+        load_directory(path)
+    } else {
+        load_jsonl(path)
+    }
+}
+
+fn load_jsonl(path: &Path) -> io::Result<Vec<Sample>> {
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str::<Sample>(l).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+        .collect()
+}
+
+fn load_directory(path: &Path) -> io::Result<Vec<Sample>> {
+    let mut documents = fs::read_dir(path)?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|p| p.is_file() && p.extension().is_none_or(|e| e != "key"))
+        .collect::<Vec<_>>();
+    documents.sort();
+
+    let mut samples = Vec::new();
+    for document in documents {
+        let key = document.with_extension("key");
+        let sibling = path.parent().map(|p| p.join("keys").join(key.file_name().unwrap()));
+        let key = match (key.is_file(), sibling) {
+            (true, _) => key,
+            (false, Some(sibling)) if sibling.is_file() => sibling,
+            _ => continue,
+        };
+        samples.push(Sample {
+            text: fs::read_to_string(&document)?,
+            keywords: fs::read_to_string(&key)?
+                .split(['\n', ';'])
+                .map(|k| k.trim().to_owned())
+                .filter(|k| !k.is_empty())
+                .collect(),
+        });
+    }
+    Ok(samples)
+}
+
+fn text_or_tokens<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Text {
+        Text(String),
+        Tokens(Vec<String>),
+    }
+    Ok(match Text::deserialize(deserializer)? {
+        Text::Text(text) => text,
+        Text::Tokens(tokens) => tokens.join(" "),
+    })
+}
+
+/// Precision, recall and F1 at each of [`CUTOFFS`], plus mean average precision.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Scores {
+    pub precision: [f64; CUTOFFS.len()],
+    pub recall: [f64; CUTOFFS.len()],
+    pub f1: [f64; CUTOFFS.len()],
+    pub map: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Report {
+    pub documents: usize,
+    pub exact: Scores,
+    pub stemmed: Scores,
+}
+
+/// Runs `yake` over every sample and macro-averages the scores.
+pub fn evaluate(yake: &mut Yake, samples: &[Sample]) -> Report {
+    let mut report = Report { documents: samples.len(), ..Report::default() };
+    for sample in samples {
+        let predicted = yake.extract(sample.text.to_owned(), Some(CUTOFFS[CUTOFFS.len() - 1]));
+        let predicted = predicted.iter().map(|r| r.keyword().to_owned()).collect::<Vec<String>>();
+        add(&mut report.exact, &score_ranking(&predicted, &sample.keywords, false));
+        add(&mut report.stemmed, &score_ranking(&predicted, &sample.keywords, true));
+    }
+    for scores in [&mut report.exact, &mut report.stemmed] {
+        let documents = samples.len().max(1) as f64;
+        for i in 0..CUTOFFS.len() {
+            scores.precision[i] /= documents;
+            scores.recall[i] /= documents;
+            scores.f1[i] /= documents;
+        }
+        scores.map /= documents;
+    }
+    report
+}
+
+fn add(total: &mut Scores, scores: &Scores) {
+    for i in 0..CUTOFFS.len() {
+        total.precision[i] += scores.precision[i];
+        total.recall[i] += scores.recall[i];
+        total.f1[i] += scores.f1[i];
+    }
+    total.map += scores.map;
+}
+
+/// Scores one ranked list of keywords against the gold keyphrases. Each gold
+/// keyphrase is matched at most once.
+pub fn score_ranking(predicted: &[String], gold: &[String], stemmed: bool) -> Scores {
+    let gold = gold.iter().map(|g| match_form(g, stemmed)).collect::<HashSet<String>>();
+    let mut matched = HashSet::<String>::new();
+    let hits = predicted
+        .iter()
+        .map(|p| {
+            let form = match_form(p, stemmed);
+            gold.contains(&form) && matched.insert(form)
+        })
+        .collect::<Vec<bool>>();
+
+    let mut scores = Scores::default();
+    for (i, k) in CUTOFFS.iter().enumerate() {
+        let returned = hits.len().min(*k);
+        let correct = hits[..returned].iter().filter(|h| **h).count() as f64;
+        scores.precision[i] = if returned > 0 { correct / returned as f64 } else { 0.0 };
+        scores.recall[i] = if !gold.is_empty() { correct / gold.len() as f64 } else { 0.0 };
+        let sum = scores.precision[i] + scores.recall[i];
+        scores.f1[i] = if sum > 0.0 { 2.0 * scores.precision[i] * scores.recall[i] / sum } else { 0.0 };
+    }
+
+    let mut correct = 0.0;
+    let mut precision_sum = 0.0;
+    for (rank, hit) in hits.iter().enumerate() {
+        if *hit {
+            correct += 1.0;
+            precision_sum += correct / (rank + 1) as f64;
+        }
+    }
+    scores.map = if !gold.is_empty() { precision_sum / gold.len() as f64 } else { 0.0 };
+    scores
+}
+
+/// Tokenizes like extraction does, so "state-of-the-art" matches the keyword
+/// "state - of - the - art", then lowercases, Porter-stemming each word when
+/// `stemmed` is set. The stemmer only handles ASCII, so other words are kept.
+fn match_form(keyphrase: &str, stemmed: bool) -> String {
+    Preprocessor::new(keyphrase.to_owned(), None, None)
+        .split_into_words()
+        .iter()
+        .map(|w| w.to_lowercase())
+        .map(|w| if stemmed && w.is_ascii() { natural::stem::get(&w) } else { w })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "documents: {}", self.documents)?;
+        writeln!(f, "{:<8} {:>3} {:>9} {:>9} {:>9}", "match", "k", "precision", "recall", "f1")?;
+        for (name, scores) in [("exact", &self.exact), ("stemmed", &self.stemmed)] {
+            for (i, k) in CUTOFFS.iter().enumerate() {
+                writeln!(f, "{:<8} {:>3} {:>9.4} {:>9.4} {:>9.4}", name, k, scores.precision[i], scores.recall[i], scores.f1[i])?;
+            }
+            writeln!(f, "{:<8} MAP {:>9.4}", name, scores.map)?;
+        }
+        Ok(())
+    }
+}
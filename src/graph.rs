@@ -0,0 +1,104 @@
+use serde::Serialize;
+
+use crate::vocabulary::{TermDictionary, TermId};
+use crate::{Cooccurrences, Features, Words};
+
+/// The term co-occurrence graph, over the same window as `context_building`.
+///
+/// Nodes are vocabulary terms; an edge from `source` to `target` counts how
+/// often `source` appeared within the window to the left of `target`.
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct Graph {
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Node {
+    id: TermId,
+    term: String,
+    tf: usize,
+    weight: f64,
+    stopword: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Edge {
+    source: TermId,
+    target: TermId,
+    weight: usize,
+}
+
+impl Graph {
+    pub fn new(words: &Words, cooccurrences: &Cooccurrences, features: &Features, terms: &TermDictionary) -> Graph {
+        let nodes = words
+            .iter()
+            .map(|(id, occurrences)| Node {
+                id: *id,
+                term: terms.term(*id).to_owned(),
+                tf: occurrences.len(),
+                weight: features[id].weight,
+                stopword: features[id].isstop,
+            })
+            .collect();
+
+        let mut edges = cooccurrences
+            .iter()
+            .filter(|((source, target), _)| words.contains_key(source) && words.contains_key(target))
+            .map(|(&(source, target), &count)| Edge { source, target, weight: count })
+            .collect::<Vec<Edge>>();
+        edges.sort_by_key(|e| (e.source, e.target));
+
+        Graph { nodes, edges }
+    }
+
+    pub fn to_graphml(&self) -> String {
+        let mut out = String::from(concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+            "  <key id=\"term\" for=\"node\" attr.name=\"term\" attr.type=\"string\"/>\n",
+            "  <key id=\"tf\" for=\"node\" attr.name=\"tf\" attr.type=\"int\"/>\n",
+            "  <key id=\"weight\" for=\"node\" attr.name=\"weight\" attr.type=\"double\"/>\n",
+            "  <key id=\"stopword\" for=\"node\" attr.name=\"stopword\" attr.type=\"boolean\"/>\n",
+            "  <key id=\"count\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>\n",
+            "  <graph edgedefault=\"directed\">\n",
+        ));
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "    <node id=\"n{}\"><data key=\"term\">{}</data><data key=\"tf\">{}</data><data key=\"weight\">{}</data><data key=\"stopword\">{}</data></node>\n",
+                node.id, escape_xml(&node.term), node.tf, node.weight, node.stopword
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "    <edge source=\"n{}\" target=\"n{}\"><data key=\"count\">{}</data></edge>\n",
+                edge.source, edge.target, edge.weight
+            ));
+        }
+        out.push_str("  </graph>\n</graphml>\n");
+        out
+    }
+
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph cooccurrence {\n");
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "  n{} [label=\"{}\", tf={}, weight={}, stopword={}];\n",
+                node.id, escape_dot(&node.term), node.tf, node.weight, node.stopword
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!("  n{} -> n{} [weight={}];\n", edge.source, edge.target, edge.weight));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;")
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
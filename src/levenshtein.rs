@@ -1,12 +1,26 @@
-use std::cmp::max;
-
-use natural::distance::levenshtein_distance;
+use std::cmp::{max, min};
 
 pub(crate) struct Levenshtein {}
 impl Levenshtein {
     pub fn ratio(seq1: String, seq2: String) -> f64 {
-        let distance = levenshtein_distance(&seq1, &seq2);
-        let length = max(seq1.len(), seq2.len());
+        let distance = Levenshtein::distance(&seq1, &seq2);
+        let length = max(seq1.chars().count(), seq2.chars().count());
         1.0 - (distance as f64 / length as f64)
     }
-}
\ No newline at end of file
+
+    /// Edit distance counted in characters. `natural`'s version indexes
+    /// characters by byte length and panics on non-ASCII text.
+    pub fn distance(seq1: &str, seq2: &str) -> usize {
+        let b = seq2.chars().collect::<Vec<char>>();
+        let mut previous = (0..=b.len()).collect::<Vec<usize>>();
+        for (i, x) in seq1.chars().enumerate() {
+            let mut current = vec![i + 1; b.len() + 1];
+            for (j, y) in b.iter().enumerate() {
+                let change = previous[j] + usize::from(x != *y);
+                current[j + 1] = min(change, min(previous[j + 1], current[j]) + 1);
+            }
+            previous = current;
+        }
+        previous[b.len()]
+    }
+}
@@ -1,27 +1,61 @@
 
 extern crate wee_alloc;
-use std::collections::{HashMap, HashSet};
-use std::cmp::{min, max};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::cmp::{min, Ordering};
 use std::iter::FromIterator;
+use std::ops::Range;
 use stats::{stddev, mean, median};
-use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+use serde::{Serialize, Deserialize, Deserializer};
 use wasm_bindgen::prelude::*;
 
+mod acronyms;
+mod asynchronous;
+mod clitics;
+mod classic;
+pub mod evaluation;
+mod graph;
 mod levenshtein;
+mod numbers;
+mod pos;
 mod preprocessor;
+mod punctuation;
+mod scorer;
+mod segtok;
 mod stopwords;
+mod surface;
+mod synonyms;
+mod typed;
+mod typescript;
+mod unicode;
+mod vocabulary;
+
+use graph::Graph;
+use numbers::NumberPolicy;
+use pos::Patterns;
+use preprocessor::{HyphenPolicy, Preprocessor};
+use punctuation::Punctuation;
+use surface::{RawForm, SurfaceForm};
+use scorer::{Document, Rake, Scorer, ScorerKind, TextRank, TfIdf};
+use synonyms::Synonyms;
+use vocabulary::{TermDictionary, TermId};
 
 type Sentences = Vec<Sentence>;
 type Candidates = HashMap<String, PreCandidate>;
-type Features =  HashMap<String, YakeCandidate>;
-type Words = HashMap<String, Vec<Occurrence>>;
-type Contexts = HashMap<String, (Vec<String>, Vec<String>)>;
+type Features =  HashMap<TermId, YakeCandidate>;
+type Words = BTreeMap<TermId, Vec<Occurrence>>;
+type Neighbours = HashMap<TermId, usize>;
+type Contexts = HashMap<TermId, (Neighbours, Neighbours)>;
+type Cooccurrences = HashMap<(TermId, TermId), usize>;
+type Weights = HashMap<String, f64>;
+type Acronyms = HashMap<String, String>;
 type Results = Vec<ResultItem>;
 type DedupeSubgram = HashMap<String, bool>;
 
 extern crate web_sys;
 
 // A macro to provide `println!(..)`-style syntax for `console.log` logging.
+#[allow(unused_macros)]
 macro_rules! log {
     ( $( $t:tt )* ) => {
         web_sys::console::log_1(&format!( $( $t )* ).into());
@@ -30,6 +64,7 @@ macro_rules! log {
 
 
 // Use `wee_alloc` as the global allocator.
+#[cfg(target_arch = "wasm32")]
 #[global_allocator]
 static ALLOC: wee_alloc::WeeAlloc = wee_alloc::WeeAlloc::INIT;
 
@@ -60,12 +95,30 @@ struct YakeCandidate {
     
 }
 
-#[wasm_bindgen]
-#[derive(PartialEq, Clone, Debug, Serialize, Deserialize)]
+/// Term frequencies across the vocabulary, which every term's features are
+/// relative to.
+#[derive(Debug, Clone, Copy)]
+struct TfStats {
+    mean: f64,
+    std: f64,
+    max: f64,
+}
+
+#[derive(PartialEq, Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ResultItem {
     raw: String,
     keyword: String,
     score: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    normalized_score: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expansion: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    surface_forms: Vec<SurfaceForm>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    stats: Option<Stats>,
 }
 impl ResultItem {
     fn new(raw: String, keyword: String, score: f64) -> ResultItem {
@@ -73,25 +126,93 @@ impl ResultItem {
             raw,
             keyword,
             score,
+            normalized_score: None,
+            aliases: Vec::new(),
+            expansion: None,
+            surface_forms: Vec::new(),
+            stats: None,
+        }
+    }
+
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn keyword(&self) -> &str {
+        &self.keyword
+    }
+
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+}
+
+/// How often and where a keyword occurs. Positions count words, punctuation
+/// included, from the start of the text.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+struct Stats {
+    occurrences: usize,
+    sentences: usize,
+    first_position: usize,
+    last_position: usize,
+    ngram_length: usize,
+}
+impl Stats {
+    fn new(candidate: &PreCandidate) -> Stats {
+        Stats {
+            occurrences: candidate.offsets.len(),
+            sentences: candidate.sentence_ids.iter().collect::<HashSet<&usize>>().len(),
+            first_position: candidate.offsets.first().copied().unwrap_or_default(),
+            last_position: candidate.offsets.last().copied().unwrap_or_default(),
+            ngram_length: candidate.lexical_form.len(),
         }
     }
 }
 
 #[derive(Debug, Clone)]
 struct Sentence {
+    /// Words as written, for reporting.
+    pub surface: Vec<String>,
+    /// Normalized words, see `Preprocessor::normalize`.
     pub words: Vec<String>,
     pub stems: Vec<String>,
+    pub ids: Vec<TermId>,
+    /// Part-of-speech tags, only filled in when `pos_patterns` are set.
+    pub tags: Vec<pos::Tag>,
+    /// Word ranges of hyphenated compounds that were split, with the
+    /// `HyphenPolicy::Both` policy.
+    pub compounds: Vec<(usize, usize)>,
+    /// Which surface words follow the previous one without whitespace.
+    pub glued: Vec<bool>,
     pub length: usize,
 }
 impl Sentence {
-    pub fn new(words: Vec<String>, stems:Option<Vec<String>>) -> Sentence {
+    pub fn new(words: Vec<String>, stems:Option<Vec<String>>, ids: Vec<TermId>) -> Sentence {
         let length = words.len();
-        let default_stems = stems.unwrap_or(Vec::<String>::new());
+        let default_stems = stems.unwrap_or_default();
         Sentence {
+            surface: words.clone(),
             words,
             length,
             stems: default_stems,
+            ids,
+            tags: Vec::new(),
+            compounds: Vec::new(),
+            glued: vec![false; length],
+        }
+    }
+
+    /// The surface words in `range` as written, "Machine-Learning" rather
+    /// than "Machine - Learning".
+    pub fn written(&self, range: Range<usize>) -> String {
+        let mut text = String::new();
+        for i in range.clone() {
+            if i > range.start && !self.glued[i] {
+                text.push(' ');
+            }
+            text.push_str(&self.surface[i]);
         }
+        text
     }
 }
 
@@ -99,22 +220,289 @@ impl Sentence {
 struct PreCandidate {
     pub surface_forms: Vec<Vec<String>>,
     pub lexical_form: Vec<String>,
+    pub term_ids: Vec<TermId>,
     pub offsets: Vec<usize>,
     pub sentence_ids: Vec<usize>,
+    /// The first occurrence as written in the text.
+    pub written: String,
+    /// Surface forms of the synonyms merged into this candidate.
+    pub aliases: Vec<String>,
+    /// Part-of-speech tags of the first occurrence, if tagging is enabled.
+    pub tags: Vec<pos::Tag>,
 }
 
 
-#[derive(Debug, Clone)]
+/// How raw scores are mapped to a `normalized_score` in `[0, 1]`, where
+/// higher means more relevant.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Normalization {
+    /// `1 / (1 + score)`.
+    Inverse,
+    /// Linear rescaling so the best candidate is 1 and the worst is 0.
+    MinMax,
+    /// `1 - rank / candidates`, ignoring the distance between scores.
+    Rank,
+}
+impl Normalization {
+    /// Normalizes `scores`, which must already be ranked best first.
+    fn apply(&self, scores: &[f64]) -> Vec<f64> {
+        match self {
+            Normalization::Inverse => scores.iter().map(|s| 1.0 / (1.0 + s)).collect(),
+            Normalization::MinMax => {
+                let best = scores.iter().copied().fold(f64::INFINITY, f64::min);
+                let worst = scores.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                scores.iter().map(|s| if worst > best { (worst - s) / (worst - best) } else { 1.0 }).collect()
+            }
+            Normalization::Rank => {
+                let count = scores.len() as f64;
+                (0..scores.len()).map(|rank| 1.0 - rank as f64 / count).collect()
+            }
+        }
+    }
+}
+
+/// How the position feature summarizes where a term occurs. Earlier terms
+/// get a lower, better, weight under every model.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum PositionModel {
+    /// Median index of the sentences the term appears in, as in YAKE.
+    #[default]
+    Median,
+    /// Word offset of the term's first occurrence.
+    FirstOccurrence,
+    /// PositionRank-style weighting: every occurrence contributes the inverse
+    /// of its word offset, so early and repeated mentions both help.
+    InversePosition,
+}
+
+/// How single words that also appear inside longer candidates are treated,
+/// so a word like "Kaggle" does not crowd out the phrases it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum SubgramPolicy {
+    /// Score subgrams like any other candidate.
+    Off,
+    /// Add this amount to the product of the subgram's term weights.
+    Additive(f64),
+    /// Multiply the subgram's score by this factor.
+    Multiplicative(f64),
+    /// Score subgrams normally, then drop every result whose words appear
+    /// contiguously in a better-ranked result.
+    RemoveContained,
+}
+impl Default for SubgramPolicy {
+    fn default() -> SubgramPolicy {
+        SubgramPolicy::Additive(5.0)
+    }
+}
+
+/// Which variant of the algorithm extracts keywords.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Mode {
+    /// This crate's pipeline, with its subgram penalty and optional
+    /// enhancements.
+    #[default]
+    Enhanced,
+    /// The reference YAKE algorithm. Only `ngram`, `stopwords`,
+    /// `remove_duplicates`, `normalization` and `threshold` apply.
+    Classic,
+}
+
+/// Options that can be overridden for a single `get_n_best_with` call.
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CallOptions {
+    position: Option<PositionModel>,
+    scorer: Option<ScorerKind>,
+    normalization: Option<Normalization>,
+    threshold: Option<f64>,
+}
+impl CallOptions {
+    fn apply(&self, config: &mut Config) {
+        config.position = self.position.unwrap_or(config.position);
+        config.scorer = self.scorer.unwrap_or(config.scorer);
+        config.normalization = self.normalization.or(config.normalization);
+        config.threshold = self.threshold.or(config.threshold);
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 struct Config {
     pub ngram: usize,
-    pub punctuation: HashSet<String>,
+    /// Characters treated as punctuation besides Unicode punctuation and
+    /// symbols.
+    pub punctuation: Punctuation,
+    #[serde(deserialize_with = "lowercase_set")]
     pub stopwords: HashSet<String>,
     pub remove_duplicates: bool,
+    pub normalization: Option<Normalization>,
+    /// Keeps only keywords better than this value: below it for raw scores,
+    /// above it for normalized ones.
+    pub threshold: Option<f64>,
+    /// Keywords that are never returned.
+    #[serde(deserialize_with = "lowercase_set")]
+    pub blocklist: HashSet<String>,
+    /// Keywords exempt from the stopword and length filters.
+    #[serde(deserialize_with = "lowercase_set")]
+    pub protected: HashSet<String>,
+    /// Per-keyword score multipliers. Lower scores are better, so values
+    /// below 1 promote a keyword and values above 1 demote it.
+    #[serde(deserialize_with = "lowercase_keys")]
+    pub boosts: HashMap<String, f64>,
+    /// Drops candidates that occur fewer times than this.
+    pub min_occurrences: usize,
+    /// Drops candidates found in fewer distinct sentences than this.
+    pub min_sentences: usize,
+    /// Drops candidates whose occurrences make up more than this share of
+    /// the words in the text, not counting punctuation.
+    pub max_frequency: Option<f64>,
+    /// Canonical keywords mapped to aliases that are merged into them.
+    pub synonyms: Synonyms,
+    /// Merges acronyms defined in the text, such as "chief executive officer
+    /// (CEO)", with their expansion.
+    pub acronyms: bool,
+    /// Keeps candidates with stopwords between their first and last word,
+    /// such as "bank of america". Edges are never stopwords.
+    pub inner_stopwords: bool,
+    /// Which candidates containing numbers, money amounts, percentages,
+    /// years or versions are kept.
+    pub numbers: NumberPolicy,
+    /// How hyphenated compounds such as "co-founder" are tokenized.
+    pub hyphens: HyphenPolicy,
+    /// Language of the text, selecting clitic rules: `"en"` strips the
+    /// possessive "'s", `"fr"` and `"it"` strip elisions like "l'". Other
+    /// codes are rejected.
+    #[serde(deserialize_with = "clitics::language")]
+    pub language: String,
+    /// Unicode normalization applied to words before they are compared.
+    pub unicode_form: Option<unicode::Form>,
+    /// Compares words fully case folded rather than lowercased.
+    pub case_folding: bool,
+    /// Compares words without diacritics, so "café" and "cafe" are one
+    /// candidate.
+    pub strip_diacritics: bool,
+    /// Part-of-speech patterns such as `(ADJ)*(NOUN)+`; when set, only
+    /// candidates matching one of them are kept.
+    pub pos_patterns: Patterns,
+    pub scorer: ScorerKind,
+    pub position: PositionModel,
+    pub mode: Mode,
+    pub subgrams: SubgramPolicy,
+    /// Which occurrence of a keyword is reported as its `raw` form.
+    pub raw_form: RawForm,
+    /// Lists every distinct surface form of a keyword, with its count, on
+    /// the result.
+    pub surface_forms: bool,
+    /// Adds occurrence counts and positions to each result as `stats`.
+    pub stats: bool,
 
     window_size: usize,
     dedupe_lim: f64,
 }
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            window_size: 2,
+            ngram: 3,
+            dedupe_lim: 0.8,
+            stopwords: stopwords::StopWords::new().words,
+            punctuation: Punctuation::default(),
+            remove_duplicates: true,
+            normalization: None,
+            threshold: None,
+            blocklist: HashSet::new(),
+            protected: HashSet::new(),
+            boosts: HashMap::new(),
+            min_occurrences: 1,
+            min_sentences: 1,
+            max_frequency: None,
+            synonyms: Synonyms::default(),
+            acronyms: false,
+            inner_stopwords: false,
+            pos_patterns: Patterns::default(),
+            numbers: NumberPolicy::Drop,
+            hyphens: HyphenPolicy::Split,
+            language: String::from("en"),
+            unicode_form: None,
+            case_folding: false,
+            strip_diacritics: false,
+            scorer: ScorerKind::Yake,
+            position: PositionModel::Median,
+            mode: Mode::Enhanced,
+            subgrams: SubgramPolicy::default(),
+            raw_form: RawForm::First,
+            surface_forms: false,
+            stats: false,
+        }
+    }
+}
 
+impl Config {
+    /// The form words are compared by, see `unicode::fold`.
+    fn fold(&self, word: &str) -> String {
+        unicode::fold(word, self.case_folding, self.strip_diacritics)
+    }
+
+    /// Folds the stopwords, keyword lists and synonyms like the words of the
+    /// text will be, so they keep matching.
+    fn folded(mut self) -> Config {
+        if self.case_folding || self.strip_diacritics {
+            let fold = |words: &HashSet<String>| words.iter().map(|w| self.fold(w)).collect::<HashSet<String>>();
+            let (stopwords, blocklist, protected) = (fold(&self.stopwords), fold(&self.blocklist), fold(&self.protected));
+            self.boosts = self.boosts.iter().map(|(k, v)| (self.fold(k), *v)).collect();
+            self.synonyms = std::mem::take(&mut self.synonyms).folded(|w| self.fold(w));
+            self.stopwords = stopwords;
+            self.blocklist = blocklist;
+            self.protected = protected;
+        }
+        self
+    }
+}
+
+fn lowercase_set<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashSet<String>, D::Error> {
+    Ok(Vec::<String>::deserialize(deserializer)?.iter().map(|t| t.to_lowercase()).collect())
+}
+
+fn lowercase_keys<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<String, f64>, D::Error> {
+    Ok(HashMap::<String, f64>::deserialize(deserializer)?.into_iter().map(|(k, v)| (k.to_lowercase(), v)).collect())
+}
+
+/// Deserializes options passed from JS. They go through `serde_json::Value`
+/// because serde_wasm_bindgen only looks up the fields a struct declares, so
+/// it never sees, let alone rejects, a misspelled key.
+fn from_options<T: DeserializeOwned>(options: JsValue) -> Result<T, JsValue> {
+    let value = serde_wasm_bindgen::from_value::<serde_json::Value>(options)?;
+    serde_json::from_value(value).map_err(|e| js_sys::Error::new(&e.to_string()).into())
+}
+
+
+/// The output of the stages before scoring.
+struct Prepared {
+    sentences: Sentences,
+    terms: TermDictionary,
+    words: Words,
+    contexts: Contexts,
+    candidates: Candidates,
+    dedupe_subgrams: DedupeSubgram,
+    acronyms: Acronyms,
+}
+impl Prepared {
+    fn document<'a>(&'a self, config: &'a Config) -> Document<'a> {
+        Document {
+            sentences: &self.sentences,
+            words: &self.words,
+            contexts: &self.contexts,
+            terms: &self.terms,
+            stopwords: &config.stopwords,
+            dedupe_subgrams: &self.dedupe_subgrams,
+            window_size: config.window_size,
+        }
+    }
+}
 
 #[wasm_bindgen]
 #[derive(Debug, Clone)]
@@ -127,82 +515,133 @@ pub struct Yake {
 impl Yake {
     #[wasm_bindgen(constructor)]
     pub fn new(ngram: Option<usize>, remove_duplicates: Option<bool>) -> Yake {
-        let default_stopwords = stopwords::StopWords::new().words;
-        let default_punctuation = HashSet::from_iter( vec!["!", "\"", "#", "$", "%", "&", "'", "(", ")", "*", "+", ",", "-", ".", "/", ":", ",", "<", "=", ">", "?", "@", "[", "\\", "]", "^", "_", "`", "{", "|", "}", "~"].iter().map(|&s| s.to_string()));
-        let default_ngram = ngram.unwrap_or(3);
-        let default_remove_duplicates = remove_duplicates.unwrap_or(true);
+        let defaults = Config::default();
         Yake {
             config: Config {
-                window_size: 2,
-                ngram: default_ngram,
-                dedupe_lim: 0.8,
-                stopwords: default_stopwords,
-                punctuation: default_punctuation,
-                remove_duplicates: default_remove_duplicates,
+                ngram: ngram.unwrap_or(defaults.ngram),
+                remove_duplicates: remove_duplicates.unwrap_or(defaults.remove_duplicates),
+                ..defaults
             },
         }
     }
 
+    /// Builds an extractor from an options object, e.g.
+    /// `Yake.with_options({ ngram: 2, normalization: "min_max", threshold: 0.5 })`.
+    /// Omitted options keep their defaults. Invalid options throw an `Error`.
+    pub fn with_options(#[wasm_bindgen(unchecked_param_type = "YakeOptions")] options: JsValue) -> Result<Yake, JsValue> {
+        Ok(Yake { config: from_options::<Config>(options)?.folded() })
+    }
+
+    /// Adds synonyms from CSV text with one canonical keyword per line
+    /// followed by its aliases, e.g. `machine learning,ML,Machine-Learning`.
+    pub fn load_synonyms_csv(&mut self, csv: String) {
+        self.config.synonyms.extend(Synonyms::from_csv(&csv).folded(|w| self.config.fold(w)));
+    }
+
+    /// Returns the `n` best keywords, best first.
+    ///
+    /// Results are ordered by ascending score. Equal scores are broken by the
+    /// earliest first occurrence in the text and then lexicographically by
+    /// keyword, so the same input always yields the same order. NaN scores are
+    /// ranked last.
+    ///
+    /// When a `threshold` is configured and `n` is omitted, every keyword that
+    /// passes the threshold is returned.
+    #[wasm_bindgen(unchecked_return_type = "ResultItem[]")]
     pub fn get_n_best(&mut self, text: String, n: Option<usize>) -> Result<JsValue, JsValue> {
-        let default_n = n.unwrap_or(10);
-        let sentences = self.build_text(text);
-        let selected_ngrams = self.ngram_selection(self.config.ngram, sentences);
-        let filtered_candidates = self.candidate_filtering(selected_ngrams.0, None, None, None, None, None);
-        let selected_candidates = self.candidate_selection(filtered_candidates);
-        let built_words = self.vocabulary_building(selected_ngrams.1);
-        let built_contexts = self.context_building(built_words.0, built_words.1);
-        let built_features = self.feature_extraction(built_contexts.0, built_contexts.1, built_contexts.2);
-        let weighted_candidates = self.candidate_weighting(built_features.0, built_features.1, selected_candidates.0, selected_candidates.1);
+        Ok(serde_wasm_bindgen::to_value(&self.extract(text, n))?)
+    }
 
-        let mut results_vec = weighted_candidates.0.clone().iter().map(|(k, v)| ResultItem::new(weighted_candidates.4.get(&k.to_string()).unwrap().to_string(),   k.to_string(), *v)).collect::<Vec<ResultItem>>();
-        results_vec.sort_by(|a, b| a.score.partial_cmp(&b.score).unwrap());
 
-        if self.config.remove_duplicates {
-            let mut non_redundant_best = Vec::<ResultItem>::new();
-            for candidate in results_vec {
-                if self.is_redundant(candidate.clone().keyword, non_redundant_best.iter().map(|x| x.keyword.to_string()).collect::<Vec<String>>()) {
-                    continue;
-                }
-                non_redundant_best.push(candidate);
+    /// Like `get_n_best`, with `position`, `scorer`, `normalization` and
+    /// `threshold` overridden for this call only, e.g.
+    /// `instance.get_n_best_with(text, 10, { position: "inverse_position" })`.
+    #[wasm_bindgen(unchecked_return_type = "ResultItem[]")]
+    pub fn get_n_best_with(
+        &mut self,
+        text: String,
+        n: Option<usize>,
+        #[wasm_bindgen(unchecked_param_type = "CallOptions")] options: JsValue,
+    ) -> Result<JsValue, JsValue> {
+        let overrides = from_options::<CallOptions>(options)?;
+        let mut call = self.clone();
+        overrides.apply(&mut call.config);
+        call.get_n_best(text, n)
+    }
 
-                if non_redundant_best.len() >= default_n {
-                    break;
-                }
-            }
-            results_vec = non_redundant_best;
+    /// Returns the term co-occurrence graph of `text`: one node per term with
+    /// its frequency and YAKE weight, and directed edges counting how often one
+    /// term appears within the window before another.
+    ///
+    /// Without a `format` the graph is returned as `{ nodes, edges }`; `"json"`,
+    /// `"graphml"` and `"dot"` return it serialized as a string instead. Any
+    /// other format throws an `Error`.
+    #[wasm_bindgen(unchecked_return_type = "Graph | string")]
+    pub fn get_graph(&mut self, text: String, format: Option<String>) -> Result<JsValue, JsValue> {
+        let (sentences, terms) = self.build_text(text);
+        let (words, sentences) = self.vocabulary_building(sentences);
+        let (contexts, words, sentences) = self.context_building(words, sentences);
+        let features = self.feature_extraction(&contexts, &words, &sentences, &terms);
+        let graph = Graph::new(&words, &Yake::cooccurrences(&sentences, self.config.window_size), &features, &terms);
+
+        match format.as_deref() {
+            None => Ok(serde_wasm_bindgen::to_value(&graph)?),
+            Some("json") => serde_json::to_string(&graph).map(JsValue::from).map_err(|e| js_sys::Error::new(&e.to_string()).into()),
+            Some("graphml") => Ok(JsValue::from(graph.to_graphml())),
+            Some("dot") => Ok(JsValue::from(graph.to_dot())),
+            Some(other) => Err(js_sys::Error::new(&format!("unknown graph format \"{}\", expected json, graphml or dot", other)).into()),
         }
+    }
 
-        let sorted_results = results_vec.iter().take(min(default_n, results_vec.len())).map(|x| ResultItem { raw: x.raw.to_owned(), keyword: x.keyword.to_owned(), score: x.score }).collect::<Vec<ResultItem>>();
+    fn build_text(&self, text: String) -> (Sentences, TermDictionary) {
+        let mut terms = TermDictionary::new();
+        let sentences = self.split_sentences(text).iter().map(|s| self.build_sentence(s, &mut terms)).collect();
+        (sentences, terms)
+    }
 
-        Ok(serde_wasm_bindgen::to_value(&sorted_results)?)
+    fn split_sentences(&self, text: String) -> Vec<String> {
+        Preprocessor::new(text, None, None).split_into_sentences()
     }
-    
 
-    fn build_text(&mut self, text: String) -> Sentences {
-        let mut sentences = Vec::<Sentence>::new();
-        let preprocessor = preprocessor::Preprocessor::new(text, None, None).split_into_sentences();
-        for sentence in preprocessor {
-            let words = preprocessor::Preprocessor::new(sentence.to_string(), None, None).split_into_words();
-            let stems = words.iter().map(|w| w.to_lowercase()).collect::<Vec<String>>();
-            let sentence = Sentence::new(words, Some(stems));
-            sentences.push(sentence);
+    fn build_sentence(&self, sentence: &str, terms: &mut TermDictionary) -> Sentence {
+        let mut preprocessor = Preprocessor {
+            hyphens: self.config.hyphens,
+            numbers: self.config.numbers,
+            language: self.config.language.to_owned(),
+            ..Preprocessor::new(sentence.to_string(), None, None)
+        };
+        let (surface, glued) = preprocessor.split_into_spaced_words();
+        let (surface, glued, compounds) = match self.config.hyphens {
+            HyphenPolicy::Both => split_compounds(surface, glued),
+            _ => (surface, glued, Vec::new()),
+        };
+        let words = surface.iter().map(|w| preprocessor.normalize(&unicode::compose(w, self.config.unicode_form))).collect::<Vec<String>>();
+        let stems = words.iter().map(|w| self.config.fold(w)).collect::<Vec<String>>();
+        let ids = stems.iter().map(|s| terms.intern(s)).collect::<Vec<TermId>>();
+        let mut sentence = Sentence::new(words, Some(stems), ids);
+        sentence.surface = surface;
+        sentence.glued = glued;
+        sentence.compounds = compounds;
+        if !self.config.pos_patterns.is_empty() {
+            sentence.tags = pos::tag(&sentence.words);
         }
-        sentences
+        sentence
     }
 
     fn candidate_selection(&mut self, mut candidates: HashMap<String, PreCandidate>) -> (HashMap<String, PreCandidate>, HashMap<String, bool>) {
         let mut dedupe_subgrams = HashMap::<String, bool>::new();
         for (k, v) in candidates.clone() {
-            if  self.config.stopwords.contains(&v.surface_forms[0][0].to_lowercase()) ||
-                self.config.stopwords.contains(&v.surface_forms[0].last().unwrap().to_lowercase()) || 
-                v.surface_forms[0][0].len() < 3 ||
-                v.surface_forms[0].last().unwrap().len() < 3 
+            if !self.config.protected.contains(&k) && (
+                self.config.stopwords.contains(&v.lexical_form[0]) ||
+                self.config.stopwords.contains(v.lexical_form.last().unwrap()) || 
+                v.lexical_form[0].len() < 3 ||
+                v.lexical_form.last().unwrap().len() < 3 )
             {
                 candidates.remove(&k);
             }
-            if v.surface_forms[0].len() > 1 {
-                for sf in v.surface_forms[0].clone() {
-                    dedupe_subgrams.insert(sf.to_string().to_lowercase(), true);
+            if v.lexical_form.len() > 1 {
+                for sf in v.lexical_form.clone() {
+                    dedupe_subgrams.insert(sf, true);
                 }
             }
         }
@@ -211,26 +650,22 @@ impl Yake {
     }
 
     fn vocabulary_building(&mut self, sentences: Vec<Sentence>) -> (Words, Sentences) {
-        let mut words = HashMap::<String, Vec<Occurrence>>::new();
-        for (idx, sentence) in sentences.clone().iter().enumerate() {
+        let mut words = Words::new();
+        for (idx, sentence) in sentences.iter().enumerate() {
             let shift = sentences[0..idx].iter().map(|s| s.length).sum::<usize>(); 
 
             for (w_idx, word) in sentence.words.iter().enumerate() {
-                if self.is_alphanum(word.to_string(), None) && HashSet::from_iter(word.split("").map(|x| x.to_string() )).intersection(&self.config.punctuation).count() == 0 {
-                    let index = word.to_lowercase();
+                let is_compound = self.config.hyphens != HyphenPolicy::Split && preprocessor::is_compound(word);
+                let is_word = is_compound || self.is_alphanum(word.to_string(), None) && !self.config.punctuation.contains(word);
+                let is_number = self.config.numbers != NumberPolicy::Drop && numbers::kind(word).is_some();
+                if is_word || is_number {
                     let new_occurrence = Occurrence {
                         shift_offset: shift + w_idx,
                         index: idx,
                         word: word.to_string(),
                         shift
                     };
-                    
-                    let object = words.get_mut(&index);
-                    if object != None {
-                        object.unwrap().push(new_occurrence)
-                    } else {
-                        words.insert( index, vec![new_occurrence]);
-                    }
+                    words.entry(sentence.ids[w_idx]).or_default().push(new_occurrence);
                 }
             }
         }
@@ -239,168 +674,207 @@ impl Yake {
     }
 
     fn context_building(&mut self, words: Words, sentences: Sentences) -> (Contexts, Words, Sentences) {
-        let cloned_sentences = sentences.clone();
         let mut contexts = Contexts::new();
-        for sentence in cloned_sentences {
-            let words = sentence.words.iter().map(|w| w.to_lowercase()).collect::<Vec<String>>();
-            let mut buffer = Vec::<String>::new();
-            for (_j, word) in words.iter().enumerate() {
-                if !words.contains(word) {
-                    buffer.clear();
-                    continue;
-                }
-
-                let min_range = max(0 as i32, buffer.len() as i32 - self.config.window_size as i32);
-                let max_range = buffer.len();
-                let buffered_words = &buffer[(min_range as usize)..max_range as usize];
-                for w in buffered_words {
-                    let entry_1 = contexts.entry(word.to_string()).or_insert((
-                        vec![w.to_string()],
-                        Vec::<String>::new(),
+        for sentence in &sentences {
+            let mut buffer = Vec::<TermId>::new();
+            for &word in &sentence.ids {
+                let min_range = buffer.len().saturating_sub(self.config.window_size);
+                for &w in &buffer[min_range..] {
+                    // The neighbour that creates an entry is counted twice, as it always has been.
+                    let entry_1 = contexts.entry(word).or_insert_with(|| (
+                        Neighbours::from([(w, 1)]),
+                        Neighbours::new(),
                     ));
-                    entry_1.0.push(w.to_string());
-                    let entry_2 = contexts.entry(w.to_string()).or_insert((
-                        Vec::<String>::new(),
-                        vec![word.to_string()],
+                    *entry_1.0.entry(w).or_insert(0) += 1;
+                    let entry_2 = contexts.entry(w).or_insert_with(|| (
+                        Neighbours::new(),
+                        Neighbours::from([(word, 1)]),
                     ));
-                    entry_2.1.push(word.to_string());
+                    *entry_2.1.entry(word).or_insert(0) += 1;
                 }
-                buffer.push(word.to_string());
+                buffer.push(word);
             }
         }
 
         (contexts, words, sentences)
     }
 
-    fn feature_extraction(&mut self, contexts: Contexts, words: Words, sentences: Sentences) -> (Features, Contexts, Words, Sentences) {
-        let tf = words.iter().map(|(_k,v)| v.len() ).collect::<Vec<usize>>();
+    /// How often each term appears at most `window` words before another,
+    /// counted once per pair of occurrences, unlike in `context_building`.
+    fn cooccurrences(sentences: &Sentences, window: usize) -> Cooccurrences {
+        let mut counts = Cooccurrences::new();
+        for sentence in sentences {
+            for (i, &word) in sentence.ids.iter().enumerate() {
+                for &left in &sentence.ids[i.saturating_sub(window)..i] {
+                    *counts.entry((left, word)).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// How often each term directly precedes another. The parts of a split
+    /// compound also count as adjacent across their hyphens, so the joined
+    /// "state-of-the-art" candidate finds "of" between "state" and "the".
+    fn bigrams(sentences: &Sentences) -> Cooccurrences {
+        let mut counts = Yake::cooccurrences(sentences, 1);
+        for sentence in sentences {
+            for &(start, end) in &sentence.compounds {
+                let parts = sentence.ids[start..end].iter().step_by(2).collect::<Vec<_>>();
+                for pair in parts.windows(2) {
+                    *counts.entry((*pair[0], *pair[1])).or_insert(0) += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    fn feature_extraction(&self, contexts: &Contexts, words: &Words, sentences: &Sentences, terms: &TermDictionary) -> Features {
+        let stats = self.tf_stats(words, terms);
+        words.iter().map(|(id, word)| (*id, self.term_features(*id, word, &stats, contexts, sentences, terms))).collect()
+    }
+
+    fn tf_stats(&self, words: &Words, terms: &TermDictionary) -> TfStats {
+        let tf = words.values().map(|v| v.len() ).collect::<Vec<usize>>();
         let tf_nsw = words.iter().filter_map(|(k,v)| {
-            if !self.config.stopwords.contains(&k.to_owned()) {
+            if !self.config.stopwords.contains(terms.term(*k)) {
                 Some(v.len())
             } else {
                 None
             }
         }).collect::<Vec<usize>>();
 
-        let std_tf = stddev(tf_nsw.iter().map(|x| *x as f64));
-        let mean_tf = mean(tf_nsw.iter().map(|x| *x as f64));
-        let max_tf = *tf.iter().max().unwrap() as f64;
-
-        let mut features = Features::new();
-        for (key, ref word) in &words {
-
-            let mut cand = YakeCandidate::default();
-            cand.isstop = self.config.stopwords.contains(key) || key.len() < 3;
-            cand.tf =  word.len() as f64;
-            cand.tf_a = 0.0;
-            cand.tf_u = 0.0;
-            for occurrence in word.clone() {
-                if occurrence.word.chars().all(|c| c.is_uppercase()) && occurrence.word.len() > 1 {
-                     cand.tf_a += 1.0;
-                }
-                if occurrence.word.chars().nth(0).unwrap_or(' ').is_uppercase() && occurrence.shift != occurrence.shift_offset {
-                    cand.tf_u += 1.0;
-                }
-            }
+        TfStats {
+            mean: mean(tf_nsw.iter().map(|x| *x as f64)),
+            std: stddev(tf_nsw.iter().map(|x| *x as f64)),
+            max: tf.iter().max().copied().unwrap_or_default() as f64,
+        }
+    }
 
-            cand.casing = cand.tf_a.max(cand.tf_u);
-            cand.casing /= 1.0 + cand.tf.ln_1p();
+    fn term_features(&self, id: TermId, word: &[Occurrence], stats: &TfStats, contexts: &Contexts, sentences: &Sentences, terms: &TermDictionary) -> YakeCandidate {
+        let (mean_tf, std_tf, max_tf) = (stats.mean, stats.std, stats.max);
+        let key = terms.term(id);
+
+        let mut cand = YakeCandidate {
+            isstop: self.config.stopwords.contains(key) || key.len() < 3,
+            tf: word.len() as f64,
+            ..Default::default()
+        };
+        cand.tf_a = 0.0;
+        cand.tf_u = 0.0;
+        for occurrence in word {
+            if occurrence.word.chars().all(|c| c.is_uppercase()) && occurrence.word.len() > 1 {
+                 cand.tf_a += 1.0;
+            }
+            if occurrence.word.chars().nth(0).unwrap_or(' ').is_uppercase() && occurrence.shift != occurrence.shift_offset {
+                cand.tf_u += 1.0;
+            }
+        }
 
-            let sentence_ids = word.iter().map(|o| o.index).collect::<HashSet<usize>>();
-            cand.position = (3.0 + median(sentence_ids.iter().map(|x| *x)).unwrap()).ln();
-            cand.position = cand.position.ln();
+        cand.casing = cand.tf_a.max(cand.tf_u);
+        cand.casing /= 1.0 + cand.tf.ln_1p();
 
-            cand.frequency = cand.tf;
-            cand.frequency /= mean_tf + std_tf;
+        let sentence_ids = word.iter().map(|o| o.index).collect::<HashSet<usize>>();
+        cand.position = match self.config.position {
+            PositionModel::Median => (3.0 + median(sentence_ids.iter().copied()).unwrap()).ln(),
+            PositionModel::FirstOccurrence => (3.0 + word[0].shift_offset as f64).ln(),
+            PositionModel::InversePosition => (3.0 + 1.0 / word.iter().map(|o| 1.0 / (o.shift_offset + 1) as f64).sum::<f64>()).ln(),
+        };
+        cand.position = cand.position.ln();
 
-            cand.wl = 0.0;
+        cand.frequency = cand.tf;
+        cand.frequency /= mean_tf + std_tf;
 
-            let ctx = contexts.get(key).unwrap();
-            let ctx_1_hash: HashSet<String> = HashSet::from_iter(ctx.clone().0);
-            if ctx.0.len() > 0 {
-                cand.wl = ctx_1_hash.len() as f64;
-                cand.wl /=  ctx.0.len() as f64;
-            }
-            cand.pl = ctx_1_hash.len() as f64 / max_tf;
+        cand.wl = 0.0;
 
-            cand.wr = 0.0;
-            let ctx_2_hash: HashSet<String> = HashSet::from_iter(ctx.clone().1);
-            if ctx.1.len() > 0 {
-                cand.wr = ctx_2_hash.len() as f64;
-                cand.wr /= ctx.1.len() as f64;
-            }
-            cand.pr = ctx_2_hash.len() as f64 / max_tf;
-
-            cand.relatedness = 1.0;
-            cand.relatedness += (cand.wr + cand.wl) * (cand.tf / max_tf);
+        let empty_context = (Neighbours::new(), Neighbours::new());
+        let ctx = contexts.get(&id).unwrap_or(&empty_context);
+        let left_total = ctx.0.values().sum::<usize>();
+        if left_total > 0 {
+            cand.wl = ctx.0.len() as f64;
+            cand.wl /= left_total as f64;
+        }
+        cand.pl = ctx.0.len() as f64 / max_tf;
 
-            cand.different = sentence_ids.len() as f64;
-            cand.different /= sentences.len() as f64;
-            cand.weight = (cand.relatedness * cand.position) / (cand.casing + (cand.frequency / cand.relatedness) + ( cand.different / cand.relatedness));
-        
-            features.insert(key.to_string(), cand);
+        cand.wr = 0.0;
+        let right_total = ctx.1.values().sum::<usize>();
+        if right_total > 0 {
+            cand.wr = ctx.1.len() as f64;
+            cand.wr /= right_total as f64;
         }
+        cand.pr = ctx.1.len() as f64 / max_tf;
 
-        (features, contexts, words, sentences )
-    }
+        cand.relatedness = 1.0;
+        cand.relatedness += (cand.wr + cand.wl) * (cand.tf / max_tf);
 
-    fn candidate_weighting(&mut self, features: Features, contexts: Contexts, candidates: Candidates, dedupe_subgram: DedupeSubgram) -> (HashMap<String, f64>,  HashMap<String, String>, HashMap<String, (Vec<String>, Vec<String>)>, HashMap<String, PreCandidate>, HashMap<String,String>) {
-        let mut final_weights = HashMap::<String, f64>::new();
-        let mut surface_to_lexical = HashMap::<String, String>::new();
-        let mut raw_lookup = HashMap::<String, String>::new();
+        cand.different = sentence_ids.len() as f64;
+        cand.different /= sentences.len() as f64;
+        cand.weight = (cand.relatedness * cand.position) / (cand.casing + (cand.frequency / cand.relatedness) + ( cand.different / cand.relatedness));
+        cand
+    }
 
-        for (_k, v) in candidates.clone() {
-                let lowercase_forms = v.surface_forms.iter().map(|w| w.join(" ").to_lowercase());
-                for (idx, candidate) in lowercase_forms.clone().enumerate() {
-                    let tf = lowercase_forms.clone().count() as f64;
-                    let tokens = v.surface_forms[idx].iter().clone().map(|w| w.to_lowercase());
-                    let mut prod_ = 1.0;
-                    let mut sum_ = 0.0;
+    /// `bigrams` counts how often each term directly precedes another.
+    fn candidate_weighting(&self, features: &Features, bigrams: &Cooccurrences, candidates: &Candidates, dedupe_subgram: &DedupeSubgram) -> Weights {
+        candidates
+            .iter()
+            .map(|(candidate, v)| (candidate.to_string(), self.candidate_weight(candidate, v, features, bigrams, dedupe_subgram)))
+            .collect()
+    }
 
-                    // Dedup Subgram; Penalize subgrams
-                    if dedupe_subgram.contains_key(&candidate) {
-                        prod_ += 5.0;
-                    }
+    fn candidate_weight(&self, candidate: &str, v: &PreCandidate, features: &Features, bigrams: &Cooccurrences, dedupe_subgram: &DedupeSubgram) -> f64 {
+        let tf = v.surface_forms.len() as f64;
+        let tokens = &v.term_ids;
+        let mut prod_ = 1.0;
+        let mut sum_ = 0.0;
 
-                    for (j, token) in tokens.clone().enumerate() {
-                        let cand_value = match features.get_key_value(&token) {
-                            Some(b) => b,
-                            None => continue,
-                        };
-                        if cand_value.1.isstop  {
-                            let term_stop = token;
-                            let mut prob_t1 = 0.0;
-                            let mut prob_t2 = 0.0;
-                            if j - 1 > 0 {
-                                let term_left = tokens.clone().nth(j-1).unwrap();
-                                prob_t1 = contexts.get(&term_left).unwrap().1.iter().filter(|w| **w == term_stop).count() as f64 / features.get(&term_left).unwrap().tf;
-                            }
-                            if j + 1 < tokens.len() {
-                                let term_right = tokens.clone().nth(j+1).unwrap();
-                                prob_t2 = contexts.get(&term_stop).unwrap().0.iter().filter(|w| **w == term_right).count() as f64 / features.get(&term_right).unwrap().tf;
-                            }
-
-                            let prob = prob_t1 * prob_t2;
-                            prod_ *= 1.0 + (1.0 - prob );
-                            sum_ -= 1.0 - prob;
-                        } else {
-                            prod_ *= cand_value.1.weight;
-                            sum_  += cand_value.1.weight;
-                        }
-                    }
-                    if sum_ == -1.0 {
-                        sum_ = 0.999999999;
-                    }
-                    let weight = prod_ / tf * (1.0 + sum_);
+        // Penalize words that also occur inside longer candidates.
+        let subgram = dedupe_subgram.contains_key(candidate);
+        if let (true, SubgramPolicy::Additive(penalty)) = (subgram, self.config.subgrams) {
+            prod_ += penalty;
+        }
 
-                  
-                    final_weights.insert(candidate.to_string(), weight);
-                    surface_to_lexical.insert(candidate.to_string(), v.lexical_form.join(" "));
-                    raw_lookup.insert(candidate.to_string(), v.surface_forms[0].join(" ").clone());
-                } 
+        for (j, token) in tokens.iter().enumerate() {
+            let cand_value = match features.get(token) {
+                Some(b) => b,
+                None => continue,
+            };
+            if cand_value.isstop  {
+                // How strongly the stopword binds its neighbours: how often
+                // the left word is followed by it, and it by the right word.
+                let term_stop = token;
+                let bigram = |left: &TermId, right: &TermId| bigrams.get(&(*left, *right)).copied().unwrap_or(0) as f64;
+                // A stopword can open or close a protected keyword, so
+                // either neighbour may be missing.
+                let left = j.checked_sub(1).and_then(|i| tokens.get(i));
+                let right = tokens.get(j + 1);
+                let prob_t1 = left.and_then(|l| features.get(l).map(|f| bigram(l, term_stop) / f.tf)).unwrap_or(0.0);
+                let prob_t2 = right.and_then(|r| features.get(r).map(|f| bigram(term_stop, r) / f.tf)).unwrap_or(0.0);
+
+                let prob = (prob_t1 * prob_t2).clamp(0.0, 1.0);
+                prod_ *= 1.0 + (1.0 - prob );
+                sum_ -= 1.0 - prob;
+            } else {
+                prod_ *= cand_value.weight;
+                sum_  += cand_value.weight;
+            }
+        }
+        if sum_ == -1.0 {
+            sum_ = 0.999999999;
         }
+        let mut weight = prod_ / tf * (1.0 + sum_);
+        if let (true, SubgramPolicy::Multiplicative(factor)) = (subgram, self.config.subgrams) {
+            weight *= factor;
+        }
+        weight
+    }
 
-        (final_weights, surface_to_lexical, contexts, candidates, raw_lookup)
+    fn candidate_boosting(&mut self, mut weights: Weights) -> Weights {
+        for (keyword, multiplier) in &self.config.boosts {
+            if let Some(weight) = weights.get_mut(keyword) {
+                *weight *= multiplier;
+            }
+        }
+        weights
     }
 
     fn is_redundant(&mut self, cand: String, prev: Vec<String>) -> bool {
@@ -414,12 +888,74 @@ impl Yake {
         false
     }
 
-    fn is_alphanum(&mut self, mut word: String, valid_punctuation_marks: Option<String>) -> bool {
+    fn is_alphanum(&mut self, word: String, valid_punctuation_marks: Option<String>) -> bool {
         let default_valid_punctuation_marks = valid_punctuation_marks.unwrap_or("-".to_owned());
-        for punct in default_valid_punctuation_marks.split("") {
-            word = word.replace(punct, "");
+        word.chars().all(|c| c.is_alphanumeric() || default_valid_punctuation_marks.contains(c))
+    }
+
+    fn acronym_detection(&mut self, sentences: &Sentences) -> Acronyms {
+        let mut acronyms = Acronyms::new();
+        if self.config.acronyms {
+            for sentence in sentences {
+                acronyms.extend(acronyms::detect(&sentence.words, &self.config.stopwords));
+            }
         }
-        word.chars().all(|c| c.is_alphanumeric())
+        acronyms
+    }
+
+    fn candidate_merging(&mut self, candidates: Candidates, acronyms: &Acronyms) -> Candidates {
+        if self.config.synonyms.is_empty() && acronyms.is_empty() {
+            return candidates;
+        }
+
+        // Acronyms are keyed by their short form; user synonyms take precedence.
+        let mut synonyms = self.config.synonyms.clone();
+        for (short, expansion) in acronyms {
+            if synonyms.canonical(short).is_none() && synonyms.canonical(expansion).is_none() {
+                synonyms.insert(short, [expansion.as_str()]);
+            }
+        }
+
+        let mut merged = Candidates::new();
+        let mut groups = HashMap::<String, Vec<PreCandidate>>::new();
+        for (k, v) in candidates {
+            match synonyms.canonical(&k) {
+                Some(canonical) => groups.entry(canonical.to_owned()).or_default().push(v),
+                None => {
+                    merged.insert(k, v);
+                }
+            }
+        }
+
+        for (canonical, mut members) in groups {
+            // The canonical form leads, so it is weighed on its own words
+            // whenever it occurs in the text; otherwise the first alias does.
+            members.sort_by_key(|m| (m.lexical_form.join(" ") != canonical, m.offsets[0]));
+            let aliases = members.iter()
+                .filter(|m| m.lexical_form.join(" ") != canonical)
+                .map(|m| m.written.to_owned())
+                .collect::<Vec<String>>();
+
+            // Occurrences stay in text order, like those of any candidate.
+            let mut occurrences = members.iter()
+                .flat_map(|m| m.offsets.iter().zip(&m.sentence_ids).zip(&m.surface_forms))
+                .map(|((&offset, &sentence_id), words)| (offset, sentence_id, words.clone()))
+                .collect::<Vec<(usize, usize, Vec<String>)>>();
+            occurrences.sort_by_key(|o| o.0);
+
+            merged.insert(canonical.to_owned(), PreCandidate {
+                lexical_form: members[0].lexical_form.clone(),
+                term_ids: members[0].term_ids.clone(),
+                written: members[0].written.to_owned(),
+                offsets: occurrences.iter().map(|o| o.0).collect(),
+                sentence_ids: occurrences.iter().map(|o| o.1).collect(),
+                surface_forms: occurrences.into_iter().map(|o| o.2).collect(),
+                aliases,
+                tags: members[0].tags.clone(),
+            });
+        }
+
+        merged
     }
 
     fn candidate_filtering(&mut self, mut candidates: Candidates ,minimum_length: Option<usize>, minimum_word_size: Option<usize>, valid_punctuation_marks: Option<String>, maximum_word_number: Option<usize>, only_alphanum: Option<bool>) -> Candidates {
@@ -431,36 +967,57 @@ impl Yake {
 
 
         for (k, v) in candidates.clone() {
-            //get the words from the first occurring surface form
-            let words = HashSet::from_iter(v.surface_forms[0].iter().map(|w| w.to_lowercase()));
-            if words.intersection(&self.config.stopwords).count() > 0 {
+            let words = HashSet::<String>::from_iter(v.lexical_form.iter().cloned());
+            let protected = self.config.protected.contains(&k);
+            if self.config.blocklist.contains(&k) {
                 candidates.remove_entry(&k);
             }
-            if words.clone().iter().any(|w| w.parse::<f64>().is_ok()) {
+            let stopwords = if self.config.inner_stopwords {
+                [&v.lexical_form[0], v.lexical_form.last().unwrap()].iter().filter(|w| self.config.stopwords.contains(**w)).count()
+            } else {
+                words.intersection(&self.config.stopwords).count()
+            };
+            if !protected && stopwords > 0 {
+                candidates.remove_entry(&k);
+            }
+            if words.iter().any(|w| !self.config.numbers.allows(w)) {
                 candidates.remove_entry(&k);
             }
-            if words.clone().iter().any(|w| HashSet::from_iter(vec![w.to_owned()]).is_subset(&self.config.punctuation)) {
+            if words.iter().any(|w| self.config.punctuation.is_punctuation(w)) {
                 candidates.remove_entry(&k);
             }
-            if words.clone().iter().map(|w| w.to_owned()).collect::<Vec<String>>().join("").len() < default_minimum_length {
+            if !protected && words.clone().iter().map(|w| w.to_owned()).collect::<Vec<String>>().join("").len() < default_minimum_length {
                 candidates.remove_entry(&k);
             }; 
-            if words.clone().iter().map(|w| w.len()).min().unwrap() < default_minimum_word_size {
+            if !protected && words.clone().iter().map(|w| w.len()).min().unwrap() < default_minimum_word_size {
+                candidates.remove_entry(&k);
+            }
+            if !protected && !self.config.pos_patterns.is_empty() && !self.config.pos_patterns.matches(&v.tags) {
                 candidates.remove_entry(&k);
             }
             if v.lexical_form.len() > default_maximum_word_number {
                 candidates.remove_entry(&k);
             } 
-            if default_only_alphanum && candidates.contains_key(&k) {
-                if words.clone().iter().any(|w| !self.is_alphanum(w.to_owned(), Some(default_valid_punctuation_marks.to_owned()))) {
-                    candidates.remove_entry(&k);
-                }
+            if default_only_alphanum && candidates.contains_key(&k) && words.clone().iter().any(|w| !self.is_alphanum(w.to_owned(), Some(default_valid_punctuation_marks.to_owned()))) {
+                candidates.remove_entry(&k);
             }
         }
 
         candidates
     }
 
+    fn frequency_filtering(&self, mut candidates: Candidates, total_words: usize) -> Candidates {
+        candidates.retain(|k, v| {
+            let occurrences = v.surface_forms.len();
+            let sentences = v.sentence_ids.iter().collect::<HashSet<&usize>>().len();
+            self.config.protected.contains(k) || (
+                occurrences >= self.config.min_occurrences &&
+                sentences >= self.config.min_sentences &&
+                self.config.max_frequency.is_none_or(|max| occurrences as f64 / total_words as f64 <= max))
+        });
+        candidates
+    }
+
     fn ngram_selection(&mut self, n: usize, sentences: Sentences) -> (Candidates, Sentences)  {
         let mut candidates = HashMap::<String, PreCandidate>::new();
         for (idx, sentence) in sentences.iter().enumerate() {
@@ -470,44 +1027,253 @@ impl Yake {
             for j in 0..sentence.length {
                 for k in j+1..min(j + 1 + skip, sentence.length + 1) {
 
-                    let words = sentence.words[j..k].to_vec();                
+                    let words = sentence.surface[j..k].to_vec();
                     let stems = sentence.stems[j..k].to_vec();
                     let sentence_id = idx;
                     let offset = j + shift;
                     let lexical_form = stems.join(" ");
-                    let candidate = candidates.get_mut(lexical_form.as_str());
-                    if candidate.is_none() {
+                    if let Some(candidate) = candidates.get_mut(lexical_form.as_str()) {
+                        candidate.surface_forms.push(words);
+                        candidate.sentence_ids.push(sentence_id);
+                        candidate.offsets.push(offset);
+                        candidate.lexical_form = stems;
+                    } else {
                         candidates.insert(lexical_form.clone(), PreCandidate {
                             lexical_form: stems,
+                            term_ids: sentence.ids[j..k].to_vec(),
                             surface_forms: vec![words],
+                            written: sentence.written(j..k),
                             sentence_ids: vec![sentence_id],
                             offsets: vec![offset],
+                            aliases: Vec::new(),
+                            tags: sentence.tags.get(j..k).map(|t| t.to_vec()).unwrap_or_default(),
                         });
-                    } else {
-                        let candidate = candidate.unwrap();
-                        candidate.surface_forms.push(words);
-                        candidate.sentence_ids.push(sentence_id);
-                        candidate.offsets.push(offset);
-                        candidate.lexical_form = stems;
                     }
                 }
             }
+
+            for &(start, end) in &sentence.compounds {
+                let words = vec![sentence.surface[start..end].concat()];
+                let lexical_form = sentence.stems[start..end].concat();
+                let candidate = candidates.entry(lexical_form.to_owned()).or_insert_with(|| PreCandidate {
+                    surface_forms: Vec::new(),
+                    written: sentence.surface[start..end].concat(),
+                    lexical_form: vec![lexical_form],
+                    // The parts, without the hyphens, carry the compound's weight.
+                    term_ids: sentence.ids[start..end].iter().step_by(2).copied().collect(),
+                    offsets: Vec::new(),
+                    sentence_ids: Vec::new(),
+                    aliases: Vec::new(),
+                    tags: sentence.tags.get(start..end).map(|t| t.iter().step_by(2).copied().collect()).unwrap_or_default(),
+                });
+                candidate.surface_forms.push(words);
+                candidate.sentence_ids.push(idx);
+                candidate.offsets.push(start + shift);
+            }
         }
         (candidates, sentences)
     }
     
 }
 
+impl Yake {
+    /// Builds an extractor from a JSON options object, as accepted by
+    /// `with_options`.
+    pub fn from_json(options: &str) -> Result<Yake, serde_json::Error> {
+        Ok(Yake { config: serde_json::from_str::<Config>(options)?.folded() })
+    }
+
+    /// Returns the `n` best keywords, best first. See `get_n_best`.
+    pub fn extract(&mut self, text: String, n: Option<usize>) -> Vec<ResultItem> {
+        let ranked = self.rank(text);
+        self.select(ranked, n)
+    }
+
+    /// Ranks every candidate of `text`, best first, with the configured
+    /// `mode`. Classic mode tokenizes the text its own way.
+    fn rank(&mut self, text: String) -> Results {
+        match self.config.mode {
+            Mode::Enhanced => {
+                let (sentences, terms) = self.build_text(text);
+                self.rank_enhanced(sentences, terms)
+            }
+            Mode::Classic => classic::rank(&text, &self.config),
+        }
+    }
+
+    /// Normalizes, thresholds and deduplicates ranked results, keeping the `n`
+    /// best.
+    fn select(&mut self, mut results_vec: Results, n: Option<usize>) -> Vec<ResultItem> {
+        let default_n = n.unwrap_or(if self.config.threshold.is_some() { usize::MAX } else { 10 });
+
+        if let Some(normalization) = self.config.normalization {
+            let scores = results_vec.iter().map(|r| r.score).collect::<Vec<f64>>();
+            for (result, normalized) in results_vec.iter_mut().zip(normalization.apply(&scores)) {
+                result.normalized_score = Some(normalized);
+            }
+        }
+        if let Some(threshold) = self.config.threshold {
+            results_vec.retain(|r| match r.normalized_score {
+                Some(normalized) => normalized > threshold,
+                None => r.score < threshold,
+            });
+        }
+
+        if self.config.remove_duplicates {
+            let mut non_redundant_best = Vec::<ResultItem>::new();
+            for candidate in results_vec {
+                let previous = non_redundant_best.iter().map(|x| x.keyword.to_string()).collect::<Vec<String>>();
+                let redundant = match self.config.mode {
+                    Mode::Enhanced => self.is_redundant(candidate.clone().keyword, previous),
+                    Mode::Classic => classic::is_redundant(&candidate.keyword, &previous),
+                };
+                if redundant {
+                    continue;
+                }
+                non_redundant_best.push(candidate);
+
+                if non_redundant_best.len() >= default_n {
+                    break;
+                }
+            }
+            results_vec = non_redundant_best;
+        }
+
+        let sorted_results = results_vec.iter().take(min(default_n, results_vec.len())).cloned().collect::<Vec<ResultItem>>();
+
+        sorted_results
+    }
+
+    /// Runs the enhanced pipeline and returns every candidate, best first.
+    fn rank_enhanced(&mut self, sentences: Sentences, terms: TermDictionary) -> Results {
+        let prepared = self.prepare(sentences, terms);
+        // Empty or punctuation-only text has nothing to score.
+        if prepared.words.is_empty() {
+            return Results::new();
+        }
+        let weights = self.weigh(&prepared);
+        self.order(&prepared, weights)
+    }
+
+    /// Selects, merges and filters the candidates and builds the vocabulary
+    /// and contexts they are scored on.
+    fn prepare(&mut self, sentences: Sentences, terms: TermDictionary) -> Prepared {
+        let acronyms = self.acronym_detection(&sentences);
+        let selected_ngrams = self.ngram_selection(self.config.ngram, sentences);
+        let merged_candidates = self.candidate_merging(selected_ngrams.0, &acronyms);
+        let filtered_candidates = self.candidate_filtering(merged_candidates, None, None, None, None, None);
+        let total_words = selected_ngrams.1.iter().flat_map(|s| &s.words).filter(|w| !self.config.punctuation.is_punctuation(w)).count();
+        let filtered_candidates = self.frequency_filtering(filtered_candidates, total_words);
+        let selected_candidates = self.candidate_selection(filtered_candidates);
+        let built_words = self.vocabulary_building(selected_ngrams.1);
+        let built_contexts = self.context_building(built_words.0, built_words.1);
+        let (contexts, words, sentences) = built_contexts;
+        let (candidates, dedupe_subgrams) = selected_candidates;
+        Prepared { sentences, terms, words, contexts, candidates, dedupe_subgrams, acronyms }
+    }
+
+    fn weigh(&self, prepared: &Prepared) -> Weights {
+        let scorer: &dyn Scorer = match self.config.scorer {
+            ScorerKind::Yake => self,
+            ScorerKind::Rake => &Rake,
+            ScorerKind::TfIdf => &TfIdf,
+            ScorerKind::TextRank => &TextRank::default(),
+        };
+        scorer.score(&prepared.document(&self.config), &prepared.candidates)
+    }
+
+    /// Boosts the weights and orders the candidates best first.
+    fn order(&mut self, prepared: &Prepared, weights: Weights) -> Results {
+        let Prepared { sentences, candidates, acronyms, .. } = prepared;
+        let weights = self.candidate_boosting(weights);
+
+        let mut ranked = weights.iter().map(|(k, v)| (k, *v, candidates[k].offsets[0])).collect::<Vec<(&String, f64, usize)>>();
+        ranked.sort_by(|a, b| compare_scores(a.1, b.1).then(a.2.cmp(&b.2)).then(a.0.cmp(b.0)));
+        if self.config.subgrams == SubgramPolicy::RemoveContained {
+            let mut kept = Vec::<Vec<&str>>::new();
+            ranked.retain(|(k, _, _)| {
+                let words = k.split(' ').collect::<Vec<&str>>();
+                let contained = kept.iter().any(|better| better.windows(words.len()).any(|w| w == words.as_slice()));
+                if !contained {
+                    kept.push(words);
+                }
+                !contained
+            });
+        }
+        let starts = sentences.iter().scan(0, |start, s| { *start += s.length; Some(*start - s.length) }).collect::<Vec<usize>>();
+        ranked.iter().map(|(k, v, _)| {
+            let candidate = &candidates[*k];
+            // Leading punctuation such as an opening quote doesn't count.
+            let initial = candidate.offsets.iter().zip(&candidate.sentence_ids).map(|(&offset, &s)| {
+                sentences[s].words[..offset - starts[s]].iter().all(|w| !w.chars().any(|c| c.is_alphanumeric()))
+            }).collect::<Vec<bool>>();
+            let raw = surface::choose(&candidate.surface_forms, &initial, self.config.raw_form).join(" ");
+            ResultItem {
+                aliases: candidate.aliases.clone(),
+                expansion: acronyms.get(*k).cloned(),
+                surface_forms: if self.config.surface_forms { surface::count(&candidate.surface_forms) } else { Vec::new() },
+                stats: self.config.stats.then(|| Stats::new(candidate)),
+                ..ResultItem::new(raw, k.to_string(), *v)
+            }
+        }).collect::<Results>()
+    }
+}
+
+impl Scorer for Yake {
+    fn score(&self, document: &Document, candidates: &Candidates) -> Weights {
+        let features = self.feature_extraction(document.contexts, document.words, document.sentences, document.terms);
+        let bigrams = Yake::bigrams(document.sentences);
+        self.candidate_weighting(&features, &bigrams, candidates, document.dedupe_subgrams)
+    }
+}
+
+/// Splits every hyphenated compound back into its parts and hyphens,
+/// returning the words, which of them are glued to the previous one, and the
+/// range each compound now spans.
+fn split_compounds(words: Vec<String>, glued: Vec<bool>) -> (Vec<String>, Vec<bool>, Vec<(usize, usize)>) {
+    let mut split = Vec::<String>::new();
+    let mut split_glued = Vec::<bool>::new();
+    let mut compounds = Vec::<(usize, usize)>::new();
+    for (word, glued) in words.into_iter().zip(glued) {
+        if preprocessor::is_compound(&word) {
+            let start = split.len();
+            for (i, part) in word.split('-').enumerate() {
+                if i > 0 {
+                    split.push("-".to_string());
+                    split_glued.push(true);
+                }
+                split.push(part.to_string());
+                split_glued.push(i > 0 || glued);
+            }
+            compounds.push((start, split.len()));
+        } else {
+            split.push(word);
+            split_glued.push(glued);
+        }
+    }
+    (split, split_glued, compounds)
+}
+
+/// Orders scores ascending, placing NaN after every real score.
+fn compare_scores(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use serde::Serialize;
     use serde_json::json;
+    use wasm_bindgen::JsCast;
     use wasm_bindgen_test::wasm_bindgen_test;
 
     use crate::{Results, ResultItem};
 
-    #[wasm_bindgen_test]
-    fn keywords() {
-        let text = r#"
+    const TEXT: &str = r#"
         Google is acquiring data science community Kaggle. Sources tell us that Google is acquiring Kaggle, a platform that hosts data science and machine learning 
         competitions. Details about the transaction remain somewhat vague, but given that Google is hosting its Cloud 
         Next conference in San Francisco this week, the official announcement could come as early as tomorrow. 
@@ -531,59 +1297,87 @@ mod tests {
         since its   launch in 2010. Investors in Kaggle include Index Ventures, SV Angel, Max Levchin, Naval Ravikant,
         Google chief economist Hal Varian, Khosla Ventures and Yuri Milner 
         "#;
-    
-        let kwds = super::Yake::new(None, None).get_n_best(text.to_string(), Some(10));
+
+    fn with_options(options: serde_json::Value) -> super::Yake {
+        let options = options.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).unwrap();
+        super::Yake::with_options(options).unwrap()
+    }
+
+    /// Every result for `text` with the given options, best first.
+    fn extract(text: &str, options: serde_json::Value) -> Results {
+        let kwds = with_options(options).get_n_best(text.to_string(), Some(usize::MAX));
+        serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap()
+    }
+
+    /// The keywords of `extract`, best first.
+    fn keywords_of(text: &str, options: serde_json::Value) -> Vec<String> {
+        extract(text, options).into_iter().map(|r| r.keyword).collect()
+    }
+
+    #[wasm_bindgen_test]
+    fn keywords() {
+        let kwds = super::Yake::new(None, None).get_n_best(TEXT.to_string(), Some(10));
         let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap());
         let results: Results = vec![
             ResultItem{
                 raw: "Kaggle".to_owned(),
                 keyword: "kaggle".to_owned(),
-                score: 0.20846279315962324
+                score: 0.20846279315962324,
+                ..Default::default()
               },
               ResultItem{
                 raw: "Google".to_owned(),
                 keyword: "google".to_owned(),
-                score: 0.23676437642810488
+                score: 0.2367643764281049,
+                ..Default::default()
               },
               ResultItem{
                 raw: "acquiring Kaggle".to_owned(),
                 keyword: "acquiring kaggle".to_owned(),
-                score: 0.3017882425537463
+                score: 0.30178824255374637,
+                ..Default::default()
               },
               ResultItem{
                 raw: "data science".to_owned(),
                 keyword: "data science".to_owned(),
-                score: 0.30873986543219967
+                score: 0.30873986543219967,
+                ..Default::default()
               },
               ResultItem{
                 raw: "Google Cloud".to_owned(),
                 keyword: "google cloud".to_owned(),
-                score: 0.40955463454967833
+                score: 0.4095546345496784,
+                ..Default::default()
               },
               ResultItem{
                 raw: "Google Cloud Platform".to_owned(),
                 keyword: "google cloud platform".to_owned(),
-                score: 0.5018536215405839
+                score: 0.5018536215405841,
+                ..Default::default()
               },
               ResultItem{
                 raw: "acquiring data science".to_owned(),
                 keyword: "acquiring data science".to_owned(),
-                score: 0.5494143207629893
+                score: 0.5494143207629895,
+                ..Default::default()
               },
               ResultItem{
                 raw: "San Francisco".to_owned(),
                 keyword: "san francisco".to_owned(),
-                score: 0.7636151899513093
+                score: 0.7636151899513093,
+                ..Default::default()
               },
               ResultItem{
                 raw: "CEO Anthony Goldbloom".to_owned(),
                 keyword: "ceo anthony goldbloom".to_owned(),
-                score: 0.8166005339007906
+                score: 0.8166005339007906,
+                ..Default::default()
               },
               ResultItem{
                 raw: "science community Kaggle".to_owned(),
                 keyword: "science community kaggle".to_owned(),
-                score: 0.8690005548383123
+                score: 0.8690005548383123,
+                ..Default::default()
               }
         ];
 
@@ -591,4 +1385,599 @@ mod tests {
         assert_eq!(value.unwrap(), results);
     }
 
-}
\ No newline at end of file
+    #[wasm_bindgen_test]
+    fn ties_break_on_first_occurrence() {
+        let kwds = super::Yake::new(Some(1), None).get_n_best("zebra apple".to_string(), None);
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        let keywords = value.iter().map(|r| r.keyword.as_str()).collect::<Vec<&str>>();
+
+        assert_eq!(value[0].score, value[1].score);
+        assert_eq!(keywords, vec!["zebra", "apple"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn nan_scores_rank_last() {
+        let mut scores = [f64::NAN, 0.5, f64::NAN, 0.1];
+        scores.sort_by(|a, b| super::compare_scores(*a, *b));
+
+        assert_eq!(scores[..2], [0.1, 0.5]);
+        assert!(scores[2..].iter().all(|s| s.is_nan()));
+    }
+
+    #[wasm_bindgen_test]
+    fn normalized_scores() {
+        let kwds = with_options(json!({ "normalization": "inverse" })).get_n_best(TEXT.to_string(), Some(5));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert!(value.iter().all(|r| r.normalized_score == Some(1.0 / (1.0 + r.score))));
+
+        for normalization in ["min_max", "rank"] {
+            let kwds = with_options(json!({ "normalization": normalization })).get_n_best(TEXT.to_string(), Some(5));
+            let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+            let normalized = value.iter().map(|r| r.normalized_score.unwrap()).collect::<Vec<f64>>();
+
+            assert_eq!(normalized[0], 1.0);
+            assert!(normalized.windows(2).all(|w| w[0] >= w[1] && w[1] >= 0.0));
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn threshold_replaces_n() {
+        let kwds = with_options(json!({ "threshold": 0.4 })).get_n_best(TEXT.to_string(), None);
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        let keywords = value.iter().map(|r| r.keyword.as_str()).collect::<Vec<&str>>();
+        assert_eq!(keywords, vec!["kaggle", "google", "acquiring kaggle", "data science"]);
+
+        let kwds = with_options(json!({ "normalization": "inverse", "threshold": 0.75 })).get_n_best(TEXT.to_string(), None);
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert_eq!(value.len(), 4);
+        assert!(value.iter().all(|r| r.normalized_score.unwrap() > 0.75));
+    }
+
+    #[wasm_bindgen_test]
+    fn empty_documents() {
+        for mode in ["enhanced", "classic"] {
+            for scorer in ["yake", "rake", "tf_idf", "text_rank"] {
+                let mut yake = with_options(json!({ "mode": mode, "scorer": scorer }));
+                for text in ["", "...", " \n\t ", "!?"] {
+                    let kwds = yake.get_n_best(text.to_string(), Some(10));
+                    assert_eq!(serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap(), Vec::new(), "{} {} {:?}", mode, scorer, text);
+                }
+            }
+        }
+        let graph = super::Yake::new(None, None).get_graph("...".to_string(), Some("json".to_string())).unwrap();
+        assert_eq!(graph.as_string().unwrap(), r#"{"nodes":[],"edges":[]}"#);
+    }
+
+    #[wasm_bindgen_test]
+    fn unknown_options_are_rejected() {
+        let typo = json!({ "thresold": 0.4 }).serialize(&serde_wasm_bindgen::Serializer::json_compatible()).unwrap();
+        let error = super::Yake::with_options(typo.clone()).unwrap_err();
+        assert!(error.unchecked_ref::<js_sys::Error>().message().as_string().unwrap().contains("thresold"));
+        assert!(super::Yake::new(None, None).get_n_best_with(TEXT.to_string(), Some(5), typo).is_err());
+        assert!(super::Yake::from_json(r#"{ "ngarm": 2 }"#).is_err());
+        assert!(super::Yake::from_json(r#"{ "language": "de" }"#).unwrap_err().to_string().contains("unknown variant `de`"));
+        assert!(super::Yake::from_json(r#"{ "language": "fr" }"#).is_ok());
+    }
+
+    #[wasm_bindgen_test]
+    fn user_keyword_lists() {
+        let kwds = with_options(json!({ "blocklist": ["Kaggle"] })).get_n_best(TEXT.to_string(), Some(1000));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert!(value.iter().all(|r| r.keyword != "kaggle"));
+
+        let kwds = with_options(json!({ "stopwords": ["Kaggle", "Google"] })).get_n_best(TEXT.to_string(), Some(1000));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert!(value.iter().all(|r| !r.keyword.starts_with("kaggle") && !r.keyword.ends_with("google")));
+
+        let kwds = with_options(json!({ "protected": ["the service"] })).get_n_best(TEXT.to_string(), Some(1000));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert!(value.iter().any(|r| r.keyword == "the service"));
+
+        let kwds = with_options(json!({ "boosts": { "San Francisco": 0.1 } })).get_n_best(TEXT.to_string(), Some(1));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert_eq!(value[0].keyword, "san francisco");
+    }
+
+    #[wasm_bindgen_test]
+    fn synonyms_merge_candidates() {
+        let text = "Machine learning is popular. Many teams use ML for forecasting. Machine-Learning tools keep improving. Forecasting with machine learning is common.";
+        let mut json_yake = with_options(json!({ "synonyms": { "machine learning": ["ML", "Machine-Learning"] } }));
+        let mut csv_yake = super::Yake::new(None, None);
+        csv_yake.load_synonyms_csv("# canonical,aliases...\nmachine learning, ML, Machine-Learning\n".to_string());
+
+        for yake in [&mut json_yake, &mut csv_yake] {
+            let kwds = yake.get_n_best(text.to_string(), Some(20));
+            let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+            let merged = value.iter().find(|r| r.keyword == "machine learning").unwrap();
+
+            assert_eq!(merged.aliases, vec!["ML", "Machine-Learning"]);
+            assert!(value.iter().all(|r| r.keyword != "ml" && r.keyword != "machine - learning"));
+        }
+
+        // Merged groups go through the same filters as any candidate, here
+        // the one against stopwords at the edges.
+        let kwds = with_options(json!({ "synonyms": { "forecasting with": ["ML"] } })).get_n_best(text.to_string(), Some(20));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert!(value.iter().all(|r| r.keyword != "forecasting with" && r.keyword != "ml"));
+
+        // Without the canonical form in the text, its aliases still are.
+        let kwds = with_options(json!({ "synonyms": { "deep learning": ["machine learning", "Machine-Learning"] } })).get_n_best(text.to_string(), Some(20));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        let merged = value.iter().find(|r| r.keyword == "deep learning").unwrap();
+        assert_eq!((merged.raw.as_str(), merged.aliases.clone()), ("Machine learning", vec!["Machine learning".to_string(), "Machine-Learning".to_string()]));
+    }
+
+    #[wasm_bindgen_test]
+    fn acronyms_merge_with_expansion() {
+        let text = "The chief executive officer (CEO) approved the budget. Later the CEO met investors. The team built NLP (natural language processing) tools for the CEO.";
+        let kwds = with_options(json!({ "acronyms": true })).get_n_best(text.to_string(), Some(20));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+
+        let ceo = value.iter().find(|r| r.keyword == "ceo").unwrap();
+        assert_eq!(ceo.expansion.as_deref(), Some("chief executive officer"));
+        assert_eq!(ceo.aliases, vec!["chief executive officer"]);
+        let nlp = value.iter().find(|r| r.keyword == "nlp").unwrap();
+        assert_eq!(nlp.expansion.as_deref(), Some("natural language processing"));
+        assert!(value.iter().all(|r| r.keyword != "chief executive officer"));
+
+        let kwds = super::Yake::new(None, None).get_n_best(text.to_string(), Some(20));
+        let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert!(value.iter().all(|r| r.expansion.is_none()));
+    }
+
+    #[wasm_bindgen_test]
+    fn alternative_scorers() {
+        let expected = [
+            ("yake", "kaggle"),
+            ("rake", "chief economist hal"),
+            ("tf_idf", "science community kaggle"),
+            ("text_rank", "science community kaggle"),
+        ];
+        for (scorer, best) in expected {
+            let kwds = with_options(json!({ "scorer": scorer })).get_n_best(TEXT.to_string(), Some(5));
+            let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+
+            assert_eq!(value.len(), 5);
+            assert_eq!(value[0].keyword, best);
+            assert!(value.windows(2).all(|w| w[0].score <= w[1].score));
+
+            // A protected stopword has no content words to score.
+            let kwds = with_options(json!({ "scorer": scorer, "protected": ["the"] })).get_n_best(TEXT.to_string(), Some(usize::MAX));
+            let value = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+            assert!(value.iter().all(|r| r.score.is_finite()), "{}", scorer);
+            if scorer != "yake" {
+                assert!(value.iter().all(|r| r.keyword != "the"), "{}", scorer);
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn cooccurrence_graph() {
+        let text = "Google acquires Kaggle. Kaggle hosts competitions.";
+        let mut yake = super::Yake::new(None, None);
+
+        let graph = serde_wasm_bindgen::from_value::<serde_json::Value>(yake.get_graph(text.to_string(), None).unwrap()).unwrap();
+        let nodes = graph["nodes"].as_array().unwrap();
+        let term = |id: &serde_json::Value| nodes.iter().find(|n| n["id"] == *id).unwrap()["term"].as_str().unwrap().to_owned();
+        let kaggle = nodes.iter().find(|n| n["term"] == "kaggle").unwrap();
+        assert_eq!(kaggle["tf"], 2);
+        assert!(kaggle["weight"].as_f64().unwrap() > 0.0);
+        let edges = graph["edges"].as_array().unwrap().iter().map(|e| ((term(&e["source"]), term(&e["target"])), e["weight"].as_u64().unwrap())).collect::<std::collections::HashMap<(String, String), u64>>();
+        // Each pair that appears once within the window weighs 1.
+        assert_eq!(edges[&("google".to_owned(), "acquires".to_owned())], 1);
+        assert_eq!(edges[&("google".to_owned(), "kaggle".to_owned())], 1);
+        assert_eq!(edges[&("kaggle".to_owned(), "hosts".to_owned())], 1);
+
+        let dot = yake.get_graph(text.to_string(), Some("dot".to_string())).unwrap().as_string().unwrap();
+        assert!(dot.starts_with("digraph cooccurrence {") && dot.contains("label=\"kaggle\""));
+        let graphml = yake.get_graph(text.to_string(), Some("graphml".to_string())).unwrap().as_string().unwrap();
+        assert!(graphml.contains("<graphml") && graphml.contains("<data key=\"term\">kaggle</data>"));
+        let json = yake.get_graph(text.to_string(), Some("json".to_string())).unwrap().as_string().unwrap();
+        let json = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!((&json["edges"], json["nodes"].as_array().unwrap().len()), (&graph["edges"], nodes.len()));
+        assert!(yake.get_graph(text.to_string(), Some("csv".to_string())).is_err_and(|e| e.is_instance_of::<js_sys::Error>()));
+    }
+
+    #[wasm_bindgen_test]
+    fn position_models() {
+        let mut yake = super::Yake::new(None, None);
+        let best = |kwds: Result<wasm_bindgen::JsValue, wasm_bindgen::JsValue>| {
+            serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap()[0].keyword.to_owned()
+        };
+
+        // The lead sentence opens with "Google", so early-mention models promote it over "Kaggle".
+        for position in ["first_occurrence", "inverse_position"] {
+            let options = json!({ "position": position }).serialize(&serde_wasm_bindgen::Serializer::json_compatible()).unwrap();
+            assert_eq!(best(yake.get_n_best_with(TEXT.to_string(), Some(10), options)), "google");
+            assert_eq!(best(with_options(json!({ "position": position })).get_n_best(TEXT.to_string(), Some(10))), "google");
+        }
+
+        // Per-call options leave the instance untouched.
+        assert_eq!(best(yake.get_n_best(TEXT.to_string(), Some(10))), "kaggle");
+    }
+
+    #[wasm_bindgen_test]
+    fn evaluation_metrics() {
+        use crate::evaluation::{evaluate, score_ranking, Sample};
+
+        let predicted = ["neural networks", "deep learning", "training"].map(String::from);
+        let gold = ["Deep Learning", "neural network"].map(String::from);
+        let exact = score_ranking(&predicted, &gold, false);
+        assert_eq!((exact.precision[0], exact.recall[0], exact.map), (1.0 / 3.0, 0.5, 0.25));
+        let stemmed = score_ranking(&predicted, &gold, true);
+        assert_eq!((stemmed.precision[0], stemmed.recall[0], stemmed.map), (2.0 / 3.0, 1.0, 1.0));
+
+        // Gold and predicted keyphrases are tokenized alike, whatever the hyphen policy.
+        let gold = ["state-of-the-art", "Google's  cloud"].map(String::from);
+        for predicted in [["state - of - the - art", "google cloud"], ["state-of-the-art", "google cloud"]] {
+            let exact = score_ranking(&predicted.map(String::from), &gold, false);
+            assert_eq!(exact.recall[0], 1.0);
+        }
+
+        let samples = [Sample { text: TEXT.to_string(), keywords: vec!["Kaggle".to_string(), "Google".to_string()] }];
+        let report = evaluate(&mut super::Yake::new(None, None), &samples);
+        assert_eq!((report.documents, report.exact.recall[0], report.exact.map), (1, 1.0, 1.0));
+    }
+
+    /// Stored inputs and outputs: classic mode against a Python port of the
+    /// reference YAKE package, enhanced mode against this crate's own earlier
+    /// output, both written by `tests/fixtures/generate_parity.py`.
+    #[wasm_bindgen_test]
+    fn parity_fixtures() {
+        let fixtures = serde_json::from_str::<serde_json::Value>(include_str!("../tests/fixtures/parity.json")).unwrap();
+        for case in fixtures["cases"].as_array().unwrap() {
+            let mut yake = with_options(case["options"].clone());
+            let n = case["n"].as_u64().map(|n| n as usize);
+            let results = serde_wasm_bindgen::from_value::<Results>(yake.get_n_best(case["text"].as_str().unwrap().to_string(), n).unwrap()).unwrap();
+            let expected = serde_json::from_value::<Results>(case["expected"].clone()).unwrap();
+
+            assert_eq!(results.len(), expected.len(), "{}", case["name"]);
+            for (result, expected) in results.iter().zip(&expected) {
+                assert_eq!((&result.raw, &result.keyword), (&expected.raw, &expected.keyword), "{}", case["name"]);
+                assert!((result.score - expected.score).abs() <= 1e-12 * expected.score.abs(), "{}: {} scored {}, expected {}", case["name"], result.keyword, result.score, expected.score);
+            }
+        }
+    }
+
+    #[wasm_bindgen_test]
+    fn classic_deduplication() {
+        use crate::classic::is_redundant;
+
+        // Two edits in 15 characters: 0.87 by edit distance, 0.93 by LCS.
+        assert!(!is_redundant("neural netwokrs", &["neural networks".to_string()]));
+        assert!(is_redundant("neural network", &["neural networks".to_string()]));
+    }
+
+    #[wasm_bindgen_test]
+    fn reference_tokenization() {
+        let text = "Reached by phone, Kaggle's co-founder\n  declined 'to comment'. It's worth $12.5 (e.g. today)...\nDr. Smith isn't at www.example.com. U.S. firms grew";
+        let expected = [
+            vec!["Reached", "by", "phone", ",", "Kaggle", "co-founder", "declined", "'", "to", "comment", "'", "."],
+            vec!["It", "worth", "$", "12.5", "(", "e.g.", "today", ")", "..."],
+            vec!["Dr.", "Smith", "is", "n't", "at", "www.example.com", "."],
+            vec!["U.S.", "firms", "grew"],
+        ];
+        assert_eq!(crate::segtok::sentences(text), expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn subgram_policies() {
+        let score = |results: &Results, keyword: &str| results.iter().find(|r| r.keyword == keyword).map(|r| r.score);
+        let subgrams = |subgrams: serde_json::Value| extract(TEXT, json!({ "subgrams": subgrams, "remove_duplicates": false }));
+
+        let off = subgrams(json!("off"));
+        let default = extract(TEXT, json!({ "remove_duplicates": false }));
+        assert_eq!(default, subgrams(json!({ "additive": 5.0 })));
+        assert_eq!(off, subgrams(json!({ "additive": 0.0 })));
+        assert!(score(&off, "kaggle").unwrap() < score(&default, "kaggle").unwrap());
+        // Only words that also occur inside longer candidates are penalized.
+        assert_eq!(score(&off, "data science"), score(&default, "data science"));
+
+        let doubled = subgrams(json!({ "multiplicative": 2.0 }));
+        assert_eq!(score(&doubled, "kaggle").unwrap(), score(&off, "kaggle").unwrap() * 2.0);
+        assert_eq!(score(&doubled, "data science"), score(&off, "data science"));
+
+        let removed = subgrams(json!("remove_contained"));
+        assert!(removed.len() < off.len());
+        for (i, result) in removed.iter().enumerate() {
+            let words = result.keyword.split(' ').collect::<Vec<&str>>();
+            assert!(removed[..i].iter().all(|better| !better.keyword.split(' ').collect::<Vec<&str>>().windows(words.len()).any(|w| w == words.as_slice())));
+        }
+        assert_eq!(score(&removed, "google"), score(&off, "google"));
+    }
+
+    #[wasm_bindgen_test]
+    fn inner_stopwords_bridge_neighbours() {
+        let text = "Bank of America raised rates on Monday. Analysts said Bank of America will keep rates high. Shares of Bank of America rose.";
+        let keywords = |options| keywords_of(text, options);
+        assert!(!keywords(json!({})).contains(&"bank of america".to_string()));
+        let inner = keywords(json!({ "inner_stopwords": true }));
+        assert!(inner.contains(&"bank of america".to_string()));
+        assert!(inner.iter().all(|k| !k.starts_with("of ") && !k.ends_with(" of")));
+
+        let mut yake = with_options(json!({ "inner_stopwords": true }));
+        let (sentences, terms) = yake.build_text(text.to_string());
+        let (bank, of, america) = (sentences[0].ids[0], sentences[0].ids[1], sentences[0].ids[2]);
+        let (candidates, sentences) = yake.ngram_selection(3, sentences);
+        let (words, sentences) = yake.vocabulary_building(sentences);
+        let (contexts, words, sentences) = yake.context_building(words, sentences);
+        let features = yake.feature_extraction(&contexts, &words, &sentences, &terms);
+        let bigrams = super::Yake::cooccurrences(&sentences, 1);
+        let weights = yake.candidate_weighting(&features, &bigrams, &candidates, &super::DedupeSubgram::new());
+
+        // "of" directly follows all 3 "bank" and precedes all 3 "america", so
+        // P = 3/3 * 3/3 = 1 and "of" neither penalizes nor rewards: the score
+        // is H(bank) * H(america) / tf * (1 + H(bank) + H(america)).
+        assert_eq!((bigrams[&(bank, of)], bigrams[&(of, america)]), (3, 3));
+        assert_eq!((features[&bank].tf, features[&america].tf), (3.0, 3.0));
+        let (h_bank, h_america) = (features[&bank].weight, features[&america].weight);
+        let expected = h_bank * h_america / 3.0 * (1.0 + h_bank + h_america);
+        assert!((weights["bank of america"] - expected).abs() <= 1e-12 * expected);
+    }
+
+    #[wasm_bindgen_test]
+    fn frequency_filters() {
+        let text = "Rust compiler checks ownership. The Rust compiler is fast. Garbage collection is absent.";
+        let keywords = |options| keywords_of(text, options);
+        let repeated = ["rust", "compiler", "rust compiler"];
+
+        let all = keywords(json!({ "remove_duplicates": false }));
+        assert!(all.contains(&"ownership".to_string()) && all.contains(&"garbage collection".to_string()));
+        for options in [json!({ "min_occurrences": 2, "remove_duplicates": false }), json!({ "min_sentences": 2, "remove_duplicates": false })] {
+            let mut frequent = keywords(options);
+            frequent.sort();
+            assert_eq!(frequent, ["compiler", "rust", "rust compiler"]);
+        }
+
+        // "rust" and "compiler" each make up 2 of the 13 words, about 0.154;
+        // counting the 3 full stops as words would make it 0.125.
+        let rare = keywords(json!({ "max_frequency": 0.14, "remove_duplicates": false }));
+        assert!(rare.contains(&"ownership".to_string()) && rare.iter().all(|k| !repeated.contains(&k.as_str())));
+
+        assert!(keywords(json!({ "min_occurrences": 2, "protected": ["ownership"] })).contains(&"ownership".to_string()));
+    }
+
+    #[wasm_bindgen_test]
+    fn pos_pattern_filtering() {
+        use crate::pos::{tag, Tag};
+
+        let words = ["Google", "is", "acquiring", "the", "famous", "Kaggle", "platform", "."].map(String::from);
+        assert_eq!(tag(&words), [Tag::Noun, Tag::Aux, Tag::Verb, Tag::Det, Tag::Adj, Tag::Propn, Tag::Noun, Tag::Punct]);
+
+        let options = json!({ "pos_patterns": ["(ADJ)*(NOUN|PROPN)+"] });
+        let keywords = keywords_of(TEXT, options);
+        assert!(keywords.contains(&"data science".to_string()) && keywords.contains(&"google cloud platform".to_string()));
+        assert!(!keywords.contains(&"acquiring kaggle".to_string()) && !keywords.contains(&"acquiring data science".to_string()));
+
+        let invalid = json!({ "pos_patterns": ["(ADJ)*(NOUNS)+"] }).serialize(&serde_wasm_bindgen::Serializer::json_compatible()).unwrap();
+        assert!(super::Yake::with_options(invalid).is_err_and(|e| e.is_instance_of::<js_sys::Error>()));
+    }
+
+    #[wasm_bindgen_test]
+    fn number_policies() {
+        use crate::numbers::{kind, Kind, NumberPolicy};
+        use crate::preprocessor::Preprocessor;
+
+        let sentence = "GPT-4 raised $12.5 million, up 20% since 2010 with v2.0 and COVID-19 data.";
+        let split = |numbers| Preprocessor { numbers, ..Preprocessor::new(sentence.to_string(), None, None) }.split_into_words();
+        let words = split(NumberPolicy::Keep);
+        for token in ["GPT-4", "$12.5", "20%", "2010", "v2.0", "COVID-19"] {
+            assert!(words.contains(&token.to_string()), "{} in {:?}", token, words);
+        }
+        let words = split(NumberPolicy::Drop);
+        for token in ["$", "12.5", "20", "%"] {
+            assert!(words.contains(&token.to_string()), "{} in {:?}", token, words);
+        }
+        let kinds = ["GPT-4", "$12.5", "20%", "2010", "v2.0", "12.5", "state"].map(kind);
+        assert_eq!(kinds, [Some(Kind::Attached), Some(Kind::Money), Some(Kind::Percent), Some(Kind::Year), Some(Kind::Version), Some(Kind::Number), None]);
+
+        let text = "OpenAI released GPT-4 in 2023. GPT-4 cost $100 million to train. Analysts compared GPT-4 with models from 2023.";
+        let keywords = |numbers: &str| keywords_of(text, json!({ "numbers": numbers }));
+        let has = |keywords: &[String], keyword: &str| keywords.contains(&keyword.to_string());
+
+        let dropped = keywords("drop");
+        assert!(!has(&dropped, "gpt-4") && !has(&dropped, "2023"));
+        let attached = keywords("attached");
+        assert!(has(&attached, "gpt-4") && !has(&attached, "2023") && !has(&attached, "$100"));
+        let kept = keywords("keep");
+        assert!(has(&kept, "gpt-4") && has(&kept, "2023") && has(&kept, "$100"));
+    }
+
+    #[wasm_bindgen_test]
+    fn hyphen_policies() {
+        use crate::preprocessor::{HyphenPolicy, Preprocessor};
+
+        let sentence = "Our co-founder built a state-of-the-art engine.";
+        let split = |hyphens| Preprocessor { hyphens, ..Preprocessor::new(sentence.to_string(), None, None) }.split_into_words();
+        assert_eq!(split(HyphenPolicy::Split), ["Our", "co", "-", "founder", "built", "a", "state", "-", "of", "-", "the", "-", "art", "engine", "."]);
+        assert_eq!(split(HyphenPolicy::Join), ["Our", "co-founder", "built", "a", "state-of-the-art", "engine", "."]);
+
+        let text = "The co-founder built a state-of-the-art search engine. Every co-founder wants a state-of-the-art product. The founder of the company wrote the engine.";
+        let keywords = |hyphens: &str| keywords_of(text, json!({ "hyphens": hyphens, "remove_duplicates": false }));
+        let has = |keywords: &[String], keyword: &str| keywords.contains(&keyword.to_string());
+
+        let split = keywords("split");
+        assert!(!has(&split, "co-founder") && !has(&split, "state-of-the-art") && has(&split, "founder"));
+        let joined = keywords("join");
+        assert!(has(&joined, "co-founder") && has(&joined, "state-of-the-art") && has(&joined, "state-of-the-art search engine"));
+        let both = keywords("both");
+        assert!(has(&both, "co-founder") && has(&both, "state-of-the-art") && has(&both, "founder") && has(&both, "search engine"));
+
+        // The joined candidate skips the hyphens, so its stopwords are bridged
+        // by the bigrams across them. Counting only adjacent words left "of"
+        // and "the" unbound, and their penalties drove the score below zero.
+        let mut yake = with_options(json!({ "hyphens": "both" }));
+        let (sentences, terms) = yake.build_text(text.to_string());
+        let (state, of) = (sentences[0].ids[6], sentences[0].ids[8]);
+        assert_eq!((terms.term(state), terms.term(of)), ("state", "of"));
+        let (candidates, sentences) = yake.ngram_selection(3, sentences);
+        let (words, sentences) = yake.vocabulary_building(sentences);
+        let (contexts, words, sentences) = yake.context_building(words, sentences);
+        let features = yake.feature_extraction(&contexts, &words, &sentences, &terms);
+        let bigrams = super::Yake::bigrams(&sentences);
+        assert_eq!(bigrams[&(state, of)], 2);
+        let adjacent = yake.candidate_weighting(&features, &super::Yake::cooccurrences(&sentences, 1), &candidates, &super::DedupeSubgram::new());
+        let bridged = yake.candidate_weighting(&features, &bigrams, &candidates, &super::DedupeSubgram::new());
+        assert!(adjacent["state-of-the-art"] < 0.0 && bridged["state-of-the-art"] > 0.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn clitics() {
+        use crate::preprocessor::Preprocessor;
+
+        let split = |text: &str, language: &str| {
+            Preprocessor { language: language.to_string(), ..Preprocessor::new(text.to_string(), None, None) }.split_into_words()
+        };
+        assert_eq!(split("Kaggle’s O'Sullivan it's", "en"), ["Kaggle", "O'Sullivan", "it"]);
+        assert_eq!(split("L'intelligence jusqu'ici", "fr"), ["intelligence", "ici"]);
+        assert_eq!(split("Dell’arte all'aperto", "it"), ["arte", "aperto"]);
+        assert_eq!(split("L'intelligence", "en"), ["L'intelligence"]);
+
+        let raw = |results: &Results, keyword: &str| results.iter().find(|r| r.keyword == keyword).map(|r| r.raw.to_owned());
+
+        let text = "Kaggle’s community hosts competitions. Data scientists love Kaggle’s competitions. O'Sullivan's team won.";
+        let results = extract(text, json!({}));
+        assert_eq!(raw(&results, "kaggle competitions").as_deref(), Some("Kaggle’s competitions"));
+        assert_eq!(raw(&results, "o'sullivan").as_deref(), Some("O'Sullivan's"));
+
+        let results = extract("La storia dell'arte moderna. Il museo dell'arte moderna apre.", json!({ "language": "it" }));
+        assert_eq!(raw(&results, "arte moderna").as_deref(), Some("dell'arte moderna"));
+    }
+
+    #[wasm_bindgen_test]
+    fn unicode_punctuation() {
+        let punctuation = crate::punctuation::Punctuation::default();
+        for mark in ["“", "”", "«", "»", "—", "…", "、", "。", ",", ";", "$"] {
+            assert!(punctuation.is_punctuation(mark), "{}", mark);
+        }
+        assert!(!punctuation.is_punctuation("ǀ") && !punctuation.contains("naïve") && punctuation.contains("co-op"));
+
+        let text = "Rust compilers «optimize» code… Rust compilers — fast、reliable。Good Rust compilers ǀ fast code.";
+        let keywords = |options| keywords_of(text, options);
+        let default = keywords(json!({ "remove_duplicates": false }));
+        assert!(default.contains(&"optimize".to_string()) && default.contains(&"reliable".to_string()));
+        assert!(default.iter().all(|k| !k.contains(['«', '»', '…', '—', '、', '。'])));
+        assert!(default.contains(&"compilers ǀ fast".to_string()));
+        let extra = keywords(json!({ "remove_duplicates": false, "punctuation": ["ǀ"] }));
+        assert!(extra.iter().all(|k| !k.contains('ǀ')) && extra.contains(&"fast code".to_string()));
+    }
+
+    #[wasm_bindgen_test]
+    fn unicode_normalization() {
+        use crate::unicode::{compose, fold, Form};
+
+        assert_eq!(compose("cafe\u{301}", Some(Form::Nfc)), "café");
+        assert_eq!(compose("Ｋａｇｇｌｅ ﬁnance", Some(Form::Nfkc)), "Kaggle finance");
+        assert_eq!(compose("ﬁnance", None), "ﬁnance");
+        assert_eq!(fold("Straße", false, false), "straße");
+        assert_eq!(fold("Straße", true, false), "strasse");
+        assert_eq!(fold("Café Crème", false, true), "cafe creme");
+
+        let text = "The café opened a second café downtown. Every cafe in town serves coffee. Ｋａｇｇｌｅ hosts ﬁnance data. Kaggle publishes finance data. The coffee shop closed.";
+        let keywords = |options| extract(text, options);
+        let default = keywords(json!({ "remove_duplicates": false }));
+        assert!(["café", "cafe", "ｋａｇｇｌｅ", "kaggle"].iter().all(|k| default.iter().any(|r| r.keyword == *k)));
+
+        let folded = keywords(json!({ "remove_duplicates": false, "unicode_form": "nfkc", "case_folding": true, "strip_diacritics": true, "blocklist": ["Café"] }));
+        assert!(folded.iter().all(|r| !["café", "ｋａｇｇｌｅ", "ﬁnance", "cafe"].contains(&r.keyword.as_str())));
+        let kaggle = folded.iter().find(|r| r.keyword == "kaggle").unwrap();
+        assert_eq!(kaggle.raw, "Ｋａｇｇｌｅ");
+        assert_eq!(folded.iter().find(|r| r.keyword == "finance data").unwrap().raw, "ﬁnance data");
+
+        let synonyms = keywords(json!({ "remove_duplicates": false, "strip_diacritics": true, "synonyms": { "Café": ["coffee shop"] } }));
+        assert!(synonyms.iter().all(|r| r.keyword != "café" && r.keyword != "coffee shop"));
+        assert_eq!(synonyms.iter().find(|r| r.keyword == "cafe").unwrap().aliases, vec!["coffee shop"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn non_ascii_deduplication() {
+        use crate::levenshtein::Levenshtein;
+
+        assert_eq!(Levenshtein::ratio("café".to_string(), "cafe".to_string()), 0.75);
+        assert_eq!(Levenshtein::ratio("straße".to_string(), "strasse".to_string()), 1.0 - 2.0 / 7.0);
+
+        let text = "The café opened a second café downtown. Every cafe in town serves coffee.";
+        let kwds = super::Yake::new(None, None).get_n_best(text.to_string(), Some(usize::MAX));
+        let keywords = serde_wasm_bindgen::from_value::<Results>(kwds.unwrap()).unwrap();
+        assert!(keywords.iter().any(|r| r.keyword == "café"));
+    }
+
+    #[wasm_bindgen_test]
+    fn raw_forms() {
+        use crate::surface::SurfaceForm;
+
+        let text = "Data science is changing research. Teams invest in data science every year. \"Data science\" budgets grow as Data Science hiring grows. DATA SCIENCE.";
+        let best = |options| extract(text, options).remove(0);
+        assert_eq!(best(json!({})).raw, "Data science");
+        assert_eq!(best(json!({ "raw_form": "most_frequent" })).raw, "Data science");
+        assert_eq!(best(json!({ "raw_form": { "casing": "title" } })).raw, "Data Science");
+        assert_eq!(best(json!({ "raw_form": { "casing": "upper" } })).raw, "DATA SCIENCE");
+        assert_eq!(best(json!({ "raw_form": "non_initial" })).raw, "data science");
+        assert!(best(json!({})).surface_forms.is_empty());
+
+        let forms = |form: &str, count| SurfaceForm { form: form.to_string(), count };
+        assert_eq!(best(json!({ "surface_forms": true })).surface_forms, [
+            forms("Data science", 2),
+            forms("data science", 1),
+            forms("Data Science", 1),
+            forms("DATA SCIENCE", 1),
+        ]);
+    }
+
+    #[wasm_bindgen_test]
+    fn candidate_stats() {
+        use super::Stats;
+
+        let text = "Data science is changing research. Teams invest in data science every year. Data science budgets grow.";
+        let results = |options| extract(text, options);
+        assert!(results(json!({})).iter().all(|r| r.stats.is_none()));
+
+        let results = results(json!({ "stats": true, "remove_duplicates": false }));
+        let stats = |keyword: &str| results.iter().find(|r| r.keyword == keyword).unwrap().stats.clone().unwrap();
+        assert_eq!(stats("data science"), Stats { occurrences: 3, sentences: 3, first_position: 0, last_position: 14, ngram_length: 2 });
+        assert_eq!(stats("changing"), Stats { occurrences: 1, sentences: 1, first_position: 3, last_position: 3, ngram_length: 1 });
+    }
+
+    #[wasm_bindgen_test]
+    async fn async_extraction() {
+        use wasm_bindgen_futures::JsFuture;
+
+        for options in [json!({}), json!({ "mode": "classic" }), json!({ "scorer": "text_rank" })] {
+            let yake = with_options(options);
+            let sync = yake.clone().get_n_best(TEXT.to_string(), Some(10)).unwrap();
+            let chunked = JsFuture::from(yake.get_n_best_async(TEXT.to_string(), Some(10), None, Some(2))).await.unwrap();
+            assert_eq!(serde_wasm_bindgen::from_value::<Results>(chunked).unwrap(), serde_wasm_bindgen::from_value::<Results>(sync).unwrap());
+        }
+
+        let yake = super::Yake::new(None, None);
+
+        let controller = web_sys::AbortController::new().unwrap();
+        let pending = yake.get_n_best_async(TEXT.to_string(), Some(10), Some(controller.signal()), Some(1));
+        controller.abort();
+        let error = JsFuture::from(pending).await.unwrap_err();
+        assert_eq!(error.unchecked_into::<js_sys::Error>().name(), "AbortError");
+    }
+
+    #[wasm_bindgen_test]
+    fn byte_input() {
+        let mut yake = super::Yake::new(None, None);
+        let expected = serde_wasm_bindgen::from_value::<Results>(yake.get_n_best(TEXT.to_string(), Some(10)).unwrap()).unwrap();
+
+        let bytes = js_sys::Uint8Array::from(TEXT.as_bytes());
+        let from_bytes = yake.get_n_best_bytes(bytes.clone().into(), Some(10)).unwrap();
+        assert_eq!(serde_wasm_bindgen::from_value::<Results>(from_bytes).unwrap(), expected);
+        let from_buffer = yake.get_n_best_bytes(bytes.buffer().into(), Some(10)).unwrap();
+        assert_eq!(serde_wasm_bindgen::from_value::<Results>(from_buffer).unwrap(), expected);
+
+        let compact = yake.get_n_best_compact(bytes.into(), Some(10)).unwrap();
+        assert_eq!(compact.length(), expected.len());
+        assert_eq!(compact.keywords().split('\n').collect::<Vec<&str>>(), expected.iter().map(|r| r.keyword.as_str()).collect::<Vec<&str>>());
+        assert_eq!(compact.raws().split('\n').next(), Some("Kaggle"));
+        assert_eq!(compact.scores().to_vec(), expected.iter().map(|r| r.score).collect::<Vec<f64>>());
+
+        let invalid = js_sys::Uint8Array::from([0x4b, 0xff, 0x61].as_slice());
+        assert!(yake.get_n_best_bytes(invalid.into(), None).is_err_and(|e| e.is_instance_of::<js_sys::Error>()));
+        assert!(yake.get_n_best_bytes(wasm_bindgen::JsValue::from_str("text"), None).is_err_and(|e| e.is_instance_of::<js_sys::TypeError>()));
+    }
+
+}
@@ -0,0 +1,89 @@
+use serde::Deserialize;
+
+/// Which candidates containing numbers are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum NumberPolicy {
+    /// Drop every candidate containing a numeric token.
+    #[default]
+    Drop,
+    /// Keep numbers, money amounts, percentages, years and versions.
+    Keep,
+    /// Keep only numbers attached to a word, such as "GPT-4" or "COVID-19".
+    Attached,
+}
+impl NumberPolicy {
+    /// Whether a candidate containing `word` may be kept.
+    pub fn allows(&self, word: &str) -> bool {
+        match (self, kind(word)) {
+            (_, None) | (NumberPolicy::Keep, _) => true,
+            (NumberPolicy::Attached, Some(kind)) => kind == Kind::Attached,
+            (NumberPolicy::Drop, Some(_)) => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Kind {
+    Number,
+    Money,
+    Percent,
+    Year,
+    Version,
+    Attached,
+}
+
+pub(crate) const CURRENCIES: [char; 5] = ['$', '€', '£', '¥', '₹'];
+
+/// Classifies a token produced by `Preprocessor::split_into_words`, or
+/// returns `None` for ordinary words.
+pub(crate) fn kind(word: &str) -> Option<Kind> {
+    if is_year(word) {
+        Some(Kind::Year)
+    } else if is_number(word) {
+        Some(Kind::Number)
+    } else if word.starts_with(CURRENCIES) && is_number(&word[word.chars().next().unwrap().len_utf8()..]) {
+        Some(Kind::Money)
+    } else if word.strip_suffix('%').is_some_and(is_number) {
+        Some(Kind::Percent)
+    } else if is_version(word) {
+        Some(Kind::Version)
+    } else if is_attached(word) {
+        Some(Kind::Attached)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn is_number(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.') && word.replace(',', "").parse::<f64>().is_ok()
+}
+
+/// "1999", or a decade like "1990s".
+fn is_year(word: &str) -> bool {
+    let digits = word.strip_suffix('s').unwrap_or(word);
+    digits.len() == 4 && digits.chars().all(|c| c.is_ascii_digit()) && ('1'..='2').contains(&digits.chars().next().unwrap())
+}
+
+/// "v2", "v2.0" or "1.2.3".
+fn is_version(word: &str) -> bool {
+    let (prefixed, number) = match word.strip_prefix(['v', 'V']) {
+        Some(number) => (true, number),
+        None => (false, word),
+    };
+    let parts = number.split('.').collect::<Vec<&str>>();
+    parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit())) && (prefixed || parts.len() > 2)
+}
+
+/// A word joined to a number by a hyphen, like "GPT-4" or "COVID-19".
+fn is_attached(word: &str) -> bool {
+    match word.rsplit_once('-') {
+        Some((head, number)) => {
+            head.chars().next().is_some_and(|c| c.is_alphabetic())
+                && head.chars().all(|c| c.is_alphanumeric())
+                && number.starts_with(|c: char| c.is_ascii_digit())
+                && is_number(number)
+        }
+        None => false,
+    }
+}
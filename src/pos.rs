@@ -0,0 +1,174 @@
+use std::fmt;
+
+use regex::Regex;
+use serde::{de, Deserialize, Deserializer};
+
+/// Universal Dependencies part-of-speech tags.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Tag {
+    Adj,
+    Adp,
+    Adv,
+    Aux,
+    Cconj,
+    Det,
+    Intj,
+    Noun,
+    Num,
+    Part,
+    Pron,
+    Propn,
+    Punct,
+    Sconj,
+    Verb,
+}
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Tag::Adj => "ADJ",
+            Tag::Adp => "ADP",
+            Tag::Adv => "ADV",
+            Tag::Aux => "AUX",
+            Tag::Cconj => "CCONJ",
+            Tag::Det => "DET",
+            Tag::Intj => "INTJ",
+            Tag::Noun => "NOUN",
+            Tag::Num => "NUM",
+            Tag::Part => "PART",
+            Tag::Pron => "PRON",
+            Tag::Propn => "PROPN",
+            Tag::Punct => "PUNCT",
+            Tag::Sconj => "SCONJ",
+            Tag::Verb => "VERB",
+        })
+    }
+}
+
+/// Tags a tokenized English sentence with a small closed-class lexicon and
+/// suffix rules. Unknown words default to nouns, so it errs towards keeping
+/// candidates rather than dropping them.
+pub(crate) fn tag(words: &[String]) -> Vec<Tag> {
+    let mut tags = Vec::<Tag>::with_capacity(words.len());
+    for (i, word) in words.iter().enumerate() {
+        let previous = tags.last().copied();
+        let tag = if word.chars().all(|c| !c.is_alphanumeric()) {
+            Tag::Punct
+        } else if word.replace(',', "").parse::<f64>().is_ok() {
+            Tag::Num
+        } else if let Some(tag) = lexicon(&word.to_lowercase()) {
+            tag
+        } else if (i > 0 && word.chars().next().is_some_and(|c| c.is_uppercase())) || is_acronym(word) {
+            Tag::Propn
+        } else {
+            by_suffix(&word.to_lowercase(), previous)
+        };
+        tags.push(tag);
+    }
+    tags
+}
+
+fn is_acronym(word: &str) -> bool {
+    word.chars().count() > 1 && word.chars().filter(|c| c.is_alphabetic()).all(|c| c.is_uppercase())
+}
+
+fn by_suffix(word: &str, previous: Option<Tag>) -> Tag {
+    let after_verb_marker = matches!(previous, Some(Tag::Aux) | Some(Tag::Part) | Some(Tag::Pron));
+    if word.ends_with("ly") && word.len() > 4 {
+        Tag::Adv
+    } else if word.ends_with("ing") && after_verb_marker {
+        Tag::Verb
+    } else if word.ends_with("ed") && word.len() > 4 {
+        if after_verb_marker || matches!(previous, Some(Tag::Noun) | Some(Tag::Propn)) {
+            Tag::Verb
+        } else {
+            Tag::Adj
+        }
+    } else if ["ous", "ful", "ive", "able", "ible", "ical", "ic", "less", "ish", "ary", "al"].iter().any(|s| word.ends_with(s) && word.len() > s.len() + 2) {
+        Tag::Adj
+    } else if after_verb_marker && previous != Some(Tag::Pron) {
+        Tag::Verb
+    } else {
+        Tag::Noun
+    }
+}
+
+fn lexicon(word: &str) -> Option<Tag> {
+    Some(match word {
+        "a" | "an" | "the" | "this" | "that" | "these" | "those" | "each" | "every" | "either" | "neither" | "some" | "any" | "no"
+        | "all" | "both" | "another" | "such" | "what" | "which" | "whose" => Tag::Det,
+        "i" | "you" | "he" | "she" | "it" | "we" | "they" | "me" | "him" | "her" | "us" | "them" | "my" | "your" | "his" | "its"
+        | "our" | "their" | "mine" | "yours" | "hers" | "ours" | "theirs" | "who" | "whom" | "myself" | "yourself" | "himself"
+        | "herself" | "itself" | "ourselves" | "themselves" | "someone" | "something" | "anyone" | "anything" | "everyone"
+        | "everything" | "nobody" | "nothing" => Tag::Pron,
+        "of" | "in" | "on" | "at" | "by" | "for" | "with" | "about" | "against" | "between" | "into" | "through" | "during"
+        | "before" | "after" | "above" | "below" | "from" | "up" | "down" | "out" | "off" | "over" | "under" | "around"
+        | "among" | "across" | "behind" | "beyond" | "near" | "toward" | "towards" | "upon" | "within" | "without" | "via"
+        | "per" | "than" | "like" => Tag::Adp,
+        "and" | "or" | "but" | "nor" | "yet" | "so" => Tag::Cconj,
+        "if" | "because" | "while" | "although" | "though" | "unless" | "since" | "whether" | "whereas" | "until" | "when"
+        | "where" => Tag::Sconj,
+        "to" | "not" | "n't" => Tag::Part,
+        "is" | "are" | "was" | "were" | "be" | "been" | "being" | "am" | "has" | "have" | "had" | "having" | "do" | "does"
+        | "did" | "will" | "would" | "shall" | "should" | "can" | "could" | "may" | "might" | "must" => Tag::Aux,
+        "very" | "also" | "just" | "only" | "even" | "still" | "already" | "always" | "never" | "often" | "sometimes" | "soon"
+        | "now" | "then" | "here" | "there" | "too" | "quite" | "rather" | "almost" | "again" | "however" | "perhaps"
+        | "why" | "how" | "more" | "most" | "less" | "least" | "well" => Tag::Adv,
+        "oh" | "hey" | "wow" | "yes" | "ok" | "okay" => Tag::Intj,
+        "one" | "two" | "three" | "four" | "five" | "six" | "seven" | "eight" | "nine" | "ten" | "hundred" | "thousand"
+        | "million" | "billion" | "first" | "second" | "third" => Tag::Num,
+        "good" | "new" | "old" | "big" | "small" | "large" | "great" | "high" | "low" | "long" | "short" | "early" | "late"
+        | "young" | "important" | "different" | "same" | "other" | "many" | "much" | "few" | "little" | "own" | "last"
+        | "next" | "main" | "major" | "full" | "free" | "open" | "real" | "true" | "clear" | "strong" | "hard" | "easy"
+        | "fast" | "slow" | "recent" | "current" | "public" | "private" | "common" | "whole" | "best" | "better" | "worse"
+        | "worst" | "deep" | "wide" | "vague" | "several" | "various" => Tag::Adj,
+        "say" | "says" | "said" | "make" | "makes" | "made" | "get" | "gets" | "got" | "go" | "goes" | "went" | "gone"
+        | "know" | "knows" | "knew" | "take" | "takes" | "took" | "see" | "sees" | "saw" | "come" | "comes" | "came"
+        | "think" | "thinks" | "thought" | "give" | "gives" | "gave" | "tell" | "tells" | "told" | "find" | "finds"
+        | "found" | "become" | "becomes" | "became" | "keep" | "keeps" | "kept" | "let" | "lets" | "seem" | "seems"
+        | "remain" | "remains" | "hosts" | "uses" | "helps" | "wants" | "needs" | "began" | "begin" | "begins" | "rose"
+        | "fell" | "held" | "brought" | "bought" | "sold" | "built" | "wrote" | "ran" => Tag::Verb,
+        _ => return None,
+    })
+}
+
+const TAGS: [&str; 15] = [
+    "ADJ", "ADP", "ADV", "AUX", "CCONJ", "DET", "INTJ", "NOUN", "NUM", "PART", "PRON", "PROPN", "PUNCT", "SCONJ", "VERB",
+];
+
+/// Tag patterns candidates must match, e.g. `(ADJ)*(NOUN)+`. Tags are
+/// combined with regex operators: `( )`, `|`, `*`, `+`, `?` and `{m,n}`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Patterns {
+    patterns: Vec<Regex>,
+}
+impl Patterns {
+    pub fn new(patterns: &[String]) -> Result<Patterns, String> {
+        let tag = Regex::new(r"[A-Za-z]+").unwrap();
+        let mut compiled = Vec::<Regex>::new();
+        for pattern in patterns {
+            let pattern = pattern.replace(' ', "");
+            if let Some(unknown) = tag.find_iter(&pattern).find(|t| !TAGS.contains(&t.as_str())) {
+                return Err(format!("unknown part-of-speech tag `{}` in `{}`", unknown.as_str(), pattern));
+            }
+            let regex = format!("^(?:{})$", tag.replace_all(&pattern, "(?:$0 )"));
+            compiled.push(Regex::new(&regex).map_err(|e| e.to_string())?);
+        }
+        Ok(Patterns { patterns: compiled })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// Whether `tags` match any pattern in full.
+    pub fn matches(&self, tags: &[Tag]) -> bool {
+        let tags = tags.iter().map(|t| format!("{} ", t)).collect::<String>();
+        self.patterns.iter().any(|p| p.is_match(&tags))
+    }
+}
+
+impl<'de> Deserialize<'de> for Patterns {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Patterns, D::Error> {
+        Patterns::new(&Vec::<String>::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
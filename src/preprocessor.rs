@@ -1,9 +1,34 @@
+use serde::Deserialize;
 use unicode_segmentation::UnicodeSegmentation;
 
+use crate::clitics;
+use crate::numbers::{is_number, kind, Kind, NumberPolicy, CURRENCIES};
+
+/// How hyphenated compounds such as "co-founder" are tokenized.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum HyphenPolicy {
+    /// Separate words, with the hyphen as a punctuation token between them.
+    #[default]
+    Split,
+    /// One word, hyphens included.
+    Join,
+    /// Separate words, plus the whole compound as an extra candidate.
+    Both,
+}
+
 pub struct Preprocessor {
     pub text: String,
+    #[allow(dead_code)]
     pub ignore_urls: bool,
+    #[allow(dead_code)]
     pub expand_contractions: bool,
+    pub hyphens: HyphenPolicy,
+    /// Money amounts, percentages and attached numbers are single tokens
+    /// unless numbers are dropped.
+    pub numbers: NumberPolicy,
+    /// Selects the clitic rules applied by `normalize`.
+    pub language: String,
 }
 
 impl Preprocessor {
@@ -14,21 +39,87 @@ impl Preprocessor {
             text,
             ignore_urls: default_ignore_urls,
             expand_contractions: default_expand_contractions,
+            hyphens: HyphenPolicy::Split,
+            numbers: NumberPolicy::Drop,
+            language: String::from("en"),
         }
     }
 
+    /// Splits the text into normalized words, see `normalize`.
     pub fn split_into_words(&mut self) -> Vec<String> {
-        self.text.split_word_bounds().filter_map(|f| {
+        self.split_into_surface_words().iter().map(|w| self.normalize(w)).collect()
+    }
+
+    /// Splits the text into words exactly as they are written.
+    pub fn split_into_surface_words(&mut self) -> Vec<String> {
+        self.split_into_spaced_words().0
+    }
+
+    /// Splits the text like `split_into_surface_words`, also telling which
+    /// words are glued to the one before, with no whitespace between them.
+    pub fn split_into_spaced_words(&mut self) -> (Vec<String>, Vec<bool>) {
+        let mut words = Vec::<String>::new();
+        let mut glued = Vec::<bool>::new();
+        let mut after_space = true;
+        for f in self.text.split_word_bounds() {
             if f.trim().is_empty() {
-                None
-            } else {
-                Some(f.trim().replace("'s", "").replace(",", "").to_string())
+                after_space = true;
+                continue;
+            }
+            words.push(f.trim().to_string());
+            glued.push(!after_space);
+            after_space = false;
+
+            // Keep money amounts, percentages and words with an attached
+            // number ("GPT-4") as single tokens.
+            let joined = entity_length(&words, &glued, self.hyphens != HyphenPolicy::Split, self.numbers != NumberPolicy::Drop);
+            if joined > 1 {
+                let start = words.len() - joined;
+                let token = words.split_off(start).concat();
+                glued.truncate(start + 1);
+                words.push(token);
             }
-        }).collect::<Vec<String>>()
+        }
+        (words, glued)
     }
     
+    /// Strips clitics such as the possessive "'s" and commas, keeping the
+    /// word's case.
+    pub fn normalize(&self, word: &str) -> String {
+        clitics::strip(word, &self.language).replace(',', "")
+    }
+
     pub fn split_into_sentences(&self) -> Vec<String> {
         let sents = self.text.trim().replace("\n", "").replace("\t", "").replace("\r", "");
         sents.unicode_sentences().map(|f| f.to_string()).collect::<Vec<String>>()
     }
-}
\ No newline at end of file
+}
+
+/// How many of the trailing `words` form one entity. `glued` marks words
+/// with no whitespace before them.
+fn entity_length(words: &[String], glued: &[bool], join_hyphens: bool, join_numbers: bool) -> usize {
+    let n = words.len();
+    let last = words[n - 1].as_str();
+    let numeric = |w: &str| matches!(kind(w), Some(Kind::Number) | Some(Kind::Year) | Some(Kind::Money));
+    let money = join_numbers && n >= 2 && words[n - 2].chars().count() == 1 && words[n - 2].starts_with(CURRENCIES) && is_number(last);
+    let percent = join_numbers && n >= 2 && last == "%" && numeric(&words[n - 2]);
+    let hyphenated = n >= 3 && glued[n - 2] && words[n - 2] == "-";
+    let attached_number = join_numbers && hyphenated && is_number(last)
+        && words[n - 3].starts_with(char::is_alphabetic) && words[n - 3].chars().all(char::is_alphanumeric);
+    let compound = hyphenated && join_hyphens && is_compound(&format!("{}-{}", words[n - 3], last));
+    if !glued[n - 1] {
+        1
+    } else if money || percent {
+        2
+    } else if attached_number || compound {
+        3
+    } else {
+        1
+    }
+}
+
+/// Whether `word` is alphanumeric parts joined by single hyphens, like
+/// "state-of-the-art".
+pub(crate) fn is_compound(word: &str) -> bool {
+    word.contains('-') && word.split('-').all(|p| !p.is_empty() && p.chars().all(char::is_alphanumeric))
+}
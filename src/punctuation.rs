@@ -0,0 +1,46 @@
+use regex::{escape, Regex};
+use serde::{de, Deserialize, Deserializer};
+
+/// Punctuation and symbol characters by Unicode general category, so quotes
+/// like “ ” « », dashes, ellipses and CJK marks like 、。 are covered.
+const CATEGORIES: &str = r"\p{P}\p{S}";
+
+/// Decides which characters are punctuation: Unicode punctuation and symbols,
+/// plus any extra characters from the options.
+#[derive(Debug, Clone)]
+pub(crate) struct Punctuation {
+    any: Regex,
+    all: Regex,
+}
+impl Punctuation {
+    pub fn new(extra: &[String]) -> Result<Punctuation, String> {
+        let extra = extra.iter().flat_map(|s| s.chars()).map(|c| escape(&c.to_string())).collect::<String>();
+        let class = format!("[{}{}]", CATEGORIES, extra);
+        Ok(Punctuation {
+            any: Regex::new(&class).map_err(|e| e.to_string())?,
+            all: Regex::new(&format!("^{}+$", class)).map_err(|e| e.to_string())?,
+        })
+    }
+
+    /// Whether `word` is made of punctuation only, like "," or "—".
+    pub fn is_punctuation(&self, word: &str) -> bool {
+        self.all.is_match(word)
+    }
+
+    /// Whether any character of `word` is punctuation.
+    pub fn contains(&self, word: &str) -> bool {
+        self.any.is_match(word)
+    }
+}
+
+impl Default for Punctuation {
+    fn default() -> Punctuation {
+        Punctuation::new(&[]).unwrap()
+    }
+}
+
+impl<'de> Deserialize<'de> for Punctuation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Punctuation, D::Error> {
+        Punctuation::new(&Vec::<String>::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
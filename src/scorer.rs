@@ -0,0 +1,134 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde::Deserialize;
+
+use crate::vocabulary::{TermDictionary, TermId};
+use crate::{Candidates, Contexts, DedupeSubgram, PreCandidate, Sentences, Weights, Words, Yake};
+
+/// Which algorithm ranks the candidates.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ScorerKind {
+    #[default]
+    Yake,
+    Rake,
+    TfIdf,
+    TextRank,
+}
+
+/// The output of the shared preprocessing stages, so every scorer sees the
+/// same sentences, vocabulary and co-occurrence windows.
+pub(crate) struct Document<'a> {
+    pub sentences: &'a Sentences,
+    pub words: &'a Words,
+    pub contexts: &'a Contexts,
+    pub terms: &'a TermDictionary,
+    pub stopwords: &'a HashSet<String>,
+    pub dedupe_subgrams: &'a DedupeSubgram,
+    pub window_size: usize,
+}
+impl Document<'_> {
+    /// Vocabulary terms that are not stopwords.
+    fn is_content(&self, id: TermId) -> bool {
+        self.words.contains_key(&id) && !self.stopwords.contains(self.terms.term(id))
+    }
+
+    fn content_ids<'c>(&'c self, candidate: &'c PreCandidate) -> impl Iterator<Item = TermId> + 'c {
+        candidate.term_ids.iter().copied().filter(move |id| self.is_content(*id))
+    }
+}
+
+/// Scores candidates. Every scorer reports lower-is-better scores, like
+/// YAKE, so ranking, normalization and thresholds work the same for all.
+pub(crate) trait Scorer {
+    fn score(&self, document: &Document, candidates: &Candidates) -> Weights;
+}
+
+/// Sums per-word scores over each candidate's content words and inverts the
+/// total, since these algorithms rank higher-is-better. Candidates without
+/// content words, such as a protected stopword, have nothing to score and
+/// are left out.
+fn inverted_sums(document: &Document, candidates: &Candidates, word_scores: &BTreeMap<TermId, f64>) -> Weights {
+    candidates
+        .iter()
+        .filter(|(_, v)| document.content_ids(v).next().is_some())
+        .map(|(k, v)| {
+            let total = document.content_ids(v).map(|id| word_scores.get(&id).copied().unwrap_or(0.0)).sum::<f64>();
+            (k.to_string(), 1.0 / total)
+        })
+        .collect()
+}
+
+/// Rapid Automatic Keyword Extraction: a word scores its degree (the length
+/// of the candidates it occurs in) over its frequency.
+pub(crate) struct Rake;
+impl Scorer for Rake {
+    fn score(&self, document: &Document, candidates: &Candidates) -> Weights {
+        let mut degree = BTreeMap::<TermId, f64>::new();
+        for v in candidates.values() {
+            let length = document.content_ids(v).count() as f64;
+            for id in document.content_ids(v) {
+                *degree.entry(id).or_insert(0.0) += length * v.surface_forms.len() as f64;
+            }
+        }
+        let word_scores = degree.into_iter().map(|(id, d)| (id, d / document.words[&id].len() as f64)).collect();
+        inverted_sums(document, candidates, &word_scores)
+    }
+}
+
+/// TF-IDF within a single document, treating each sentence as a document
+/// for the inverse document frequency.
+pub(crate) struct TfIdf;
+impl Scorer for TfIdf {
+    fn score(&self, document: &Document, candidates: &Candidates) -> Weights {
+        let sentences = document.sentences.len() as f64;
+        let word_scores = document
+            .words
+            .iter()
+            .map(|(id, occurrences)| {
+                let df = occurrences.iter().map(|o| o.index).collect::<HashSet<usize>>().len() as f64;
+                (*id, occurrences.len() as f64 * ((sentences / df).ln() + 1.0))
+            })
+            .collect();
+        inverted_sums(document, candidates, &word_scores)
+    }
+}
+
+/// TextRank: PageRank over the co-occurrence graph of content words.
+pub(crate) struct TextRank {
+    pub damping: f64,
+    pub iterations: usize,
+}
+impl Default for TextRank {
+    fn default() -> TextRank {
+        TextRank { damping: 0.85, iterations: 50 }
+    }
+}
+impl Scorer for TextRank {
+    fn score(&self, document: &Document, candidates: &Candidates) -> Weights {
+        // Every content word is a node, so none is left unscored. Edges are
+        // undirected and weighted by how often two terms co-occur, counted
+        // once per pair like the graph's, not twice like in the contexts.
+        let mut edges = document.words.keys().filter(|id| document.is_content(**id)).map(|id| (*id, BTreeMap::new())).collect::<BTreeMap<TermId, BTreeMap<TermId, f64>>>();
+        for (&(left, right), &count) in &Yake::cooccurrences(document.sentences, document.window_size) {
+            if left != right && document.is_content(left) && document.is_content(right) {
+                *edges.entry(left).or_default().entry(right).or_insert(0.0) += count as f64;
+                *edges.entry(right).or_default().entry(left).or_insert(0.0) += count as f64;
+            }
+        }
+
+        let nodes = edges.len().max(1) as f64;
+        let out_weight = edges.iter().map(|(id, e)| (*id, e.values().sum::<f64>())).collect::<BTreeMap<TermId, f64>>();
+        let mut ranks = edges.keys().map(|id| (*id, 1.0 / nodes)).collect::<BTreeMap<TermId, f64>>();
+        for _ in 0..self.iterations {
+            ranks = edges
+                .iter()
+                .map(|(id, neighbours)| {
+                    let incoming = neighbours.iter().map(|(n, w)| w / out_weight[n] * ranks[n]).sum::<f64>();
+                    (*id, (1.0 - self.damping) / nodes + self.damping * incoming)
+                })
+                .collect();
+        }
+        inverted_sums(document, candidates, &ranks)
+    }
+}
@@ -0,0 +1,194 @@
+//! The tokenization of the reference YAKE package, which splits text with
+//! segtok's `split_multi`, `web_tokenizer` and `split_contractions`, so that
+//! classic mode sees the same words the reference does.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Characters that end a sentence.
+const TERMINALS: &str = ".!?\u{2026}\u{203C}\u{203D}\u{2047}\u{2048}\u{2049}\u{3002}\u{FE52}\u{FE57}\u{FF01}\u{FF0E}\u{FF1F}\u{FF61}";
+/// Characters kept inside a word when letters or digits surround them.
+const HYPHENS: [char; 9] = ['-', '\u{AD}', '\u{58A}', '\u{5BE}', '\u{1806}', '\u{2010}', '\u{2011}', '\u{2012}', '\u{30A0}'];
+const APOSTROPHES: [char; 6] = ['\'', '\u{B4}', '\u{2B9}', '\u{2BC}', '\u{2019}', '\u{2032}'];
+const CLOSING: [char; 6] = ['\'', '"', '\u{2019}', '\u{201D}', ')', ']'];
+/// Abbreviations that never end a sentence, as in "Dr. Smith".
+const TITLES: [&str; 17] = ["mr", "mrs", "ms", "dr", "prof", "st", "jr", "sr", "gen", "rev", "sen", "rep", "gov", "capt", "col", "lt", "sgt"];
+/// Abbreviations that end a sentence only before a capitalized word.
+const ABBREVIATIONS: [&str; 14] = ["etc", "vs", "inc", "ltd", "co", "corp", "no", "fig", "approx", "ca", "cf", "al", "eg", "ie"];
+
+static PARAGRAPH: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n\s*\n").unwrap());
+static TERMINAL: LazyLock<Regex> = LazyLock::new(|| Regex::new(&format!(r#"[{}]+['"\u{{2019}}\u{{201D}}]?[)\]]*\s+"#, TERMINALS)).unwrap());
+static URI: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)(?:https?://|ftp://|www\.)[^\s<>]*[^\s<>.,;:!?'\x22)\]]|[\w.+-]+@[\w-]+(?:\.[\w-]+)+").unwrap());
+static NEGATION: LazyLock<Regex> = LazyLock::new(|| Regex::new(&format!(r"(?i)^(\w+)(n[{}]t)$", APOSTROPHES.iter().collect::<String>())).unwrap());
+static CLITIC: LazyLock<Regex> = LazyLock::new(|| Regex::new(&format!(r"(?i)^(\w+)([{}](?:s|m|d|ll|re|ve))$", APOSTROPHES.iter().collect::<String>())).unwrap());
+static REFERENCE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"&(?:#(\d+)|#[xX]([0-9a-fA-F]+)|(amp|lt|gt|quot|apos|nbsp));").unwrap());
+
+/// The sentences of `text` split into words the way the reference does,
+/// without the clitics such as "'s" that it drops.
+pub(crate) fn sentences(text: &str) -> Vec<Vec<String>> {
+    split_multi(&pre_filter(text))
+        .iter()
+        .map(|s| {
+            split_contractions(web_tokenizer(s))
+                .into_iter()
+                // Only clitics with a straight apostrophe are dropped.
+                .filter(|w| !w.is_empty() && (!w.starts_with('\'') || w.chars().count() == 1))
+                .collect()
+        })
+        .collect()
+}
+
+/// Joins the lines of `text`, starting a new paragraph at every line that
+/// begins with a capital letter.
+fn pre_filter(text: &str) -> String {
+    let mut buffer = String::new();
+    for part in text.split('\n') {
+        let capitalized = part.trim_start().starts_with(|c: char| c.is_ascii_uppercase());
+        buffer.push_str(if capitalized { "\n\n" } else { " " });
+        buffer.push_str(&part.replace('\t', " "));
+    }
+    buffer
+}
+
+/// Splits paragraphs at blank lines and sentences at terminals followed by
+/// whitespace, except after initials and abbreviations.
+fn split_multi(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    for paragraph in PARAGRAPH.split(text) {
+        let mut last: Option<String> = None;
+        let mut start = 0;
+        let ends = TERMINAL.find_iter(paragraph).map(|m| m.end()).chain([paragraph.len()]);
+        for end in ends {
+            let span = &paragraph[start..end];
+            start = end;
+            match last.as_mut() {
+                Some(last) if continues(last, span) => last.push_str(span),
+                _ => sentences.extend(last.replace(span.to_owned())),
+            }
+        }
+        sentences.extend(last);
+    }
+    sentences.into_iter().map(|s| s.trim().to_owned()).filter(|s| !s.is_empty()).collect()
+}
+
+/// Whether `next` continues the sentence `last`, which ends in an initial or
+/// an abbreviation.
+fn continues(last: &str, next: &str) -> bool {
+    let word = last.split_whitespace().last().unwrap_or_default();
+    let Some(stem) = word.strip_suffix('.') else { return false };
+    let stem = stem.trim_start_matches(['(', '[', '"', '\'', '\u{201C}', '\u{2018}']);
+    let lowercase = next.trim_start().starts_with(|c: char| c.is_lowercase() || c.is_numeric());
+    let abbreviation = stem.to_lowercase().replace('.', "");
+    if stem.chars().count() == 1 && stem.chars().all(char::is_alphabetic) || TITLES.contains(&abbreviation.as_str()) {
+        return true;
+    }
+    let dotted = stem.contains('.') && stem.split('.').all(|p| p.chars().count() <= 2 && p.chars().all(char::is_alphabetic));
+    lowercase && (dotted || ABBREVIATIONS.contains(&abbreviation.as_str()))
+}
+
+/// Splits a sentence into words, keeping URLs and e-mail addresses whole.
+fn web_tokenizer(sentence: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    for m in URI.find_iter(sentence) {
+        tokens.extend(word_tokenizer(&unescape(&sentence[start..m.start()])));
+        tokens.push(m.as_str().to_owned());
+        start = m.end();
+    }
+    tokens.extend(word_tokenizer(&unescape(&sentence[start..])));
+    splice_terminal(&mut tokens);
+    tokens
+}
+
+/// Splits text at whitespace and around symbols. Hyphens, commas and dots
+/// between letters or digits stay inside words, as do apostrophes between
+/// letters, colons between digits and a dot after a letter, as in "e.g.".
+/// Runs of dots, like an ellipsis, are one token.
+fn word_tokenizer(text: &str) -> Vec<String> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let mut tokens = Vec::new();
+    for span in text.split_whitespace() {
+        let chars = span.chars().collect::<Vec<char>>();
+        let mut token = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let previous = i.checked_sub(1).map(|p| chars[p]);
+            let next = chars.get(i + 1).copied();
+            let between = |test: fn(char) -> bool| previous.is_some_and(test) && next.is_some_and(test);
+            let word = token.chars().next().is_some_and(is_word);
+            let inner = word && match c {
+                c if is_word(c) => true,
+                '.' => next != Some('.') && (between(char::is_alphanumeric) || previous.is_some_and(char::is_alphabetic)),
+                ',' => between(char::is_alphanumeric),
+                ':' => between(|c| c.is_ascii_digit()),
+                c if HYPHENS.contains(&c) => between(char::is_alphanumeric),
+                c if APOSTROPHES.contains(&c) => between(char::is_alphabetic),
+                _ => false,
+            };
+            let dots = c == '.' && token.chars().all(|t| t == '.') && !token.is_empty();
+            if !token.is_empty() && !inner && !dots {
+                tokens.push(std::mem::take(&mut token));
+            }
+            token.push(c);
+            let ellipsis = c == '.' && next == Some('.');
+            if !(is_word(c) || inner || ellipsis) {
+                tokens.push(std::mem::take(&mut token));
+            }
+        }
+        if !token.is_empty() {
+            tokens.push(token);
+        }
+    }
+    tokens
+}
+
+/// Splits the sentence terminal off the last word, as in "Kaggle.", looking
+/// past closing quotes and brackets.
+fn splice_terminal(tokens: &mut Vec<String>) {
+    let last = tokens.len();
+    for i in (last.saturating_sub(3)..last).rev() {
+        if tokens[i].chars().all(|c| CLOSING.contains(&c)) {
+            continue;
+        }
+        if tokens[i].chars().count() > 1 && tokens[i].ends_with('.') && !tokens[i].ends_with("..") {
+            tokens[i].pop();
+            tokens.insert(i + 1, ".".to_owned());
+        }
+        break;
+    }
+}
+
+/// Splits contractions such as "don't" into "do" and "n't", and possessives
+/// such as "Kaggle's" into "Kaggle" and "'s".
+fn split_contractions(tokens: Vec<String>) -> Vec<String> {
+    let mut split = Vec::new();
+    for token in tokens {
+        match NEGATION.captures(&token).or_else(|| CLITIC.captures(&token)) {
+            Some(parts) => split.extend([parts[1].to_owned(), parts[2].to_owned()]),
+            None => split.push(token),
+        }
+    }
+    split
+}
+
+/// Decodes the HTML character references common in web text.
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_owned();
+    }
+    REFERENCE
+        .replace_all(text, |c: &regex::Captures| {
+            let code = match (c.get(1), c.get(2), c.get(3).map(|m| m.as_str())) {
+                (Some(decimal), _, _) => decimal.as_str().parse::<u32>().ok(),
+                (_, Some(hex), _) => u32::from_str_radix(hex.as_str(), 16).ok(),
+                (_, _, Some("amp")) => Some('&' as u32),
+                (_, _, Some("lt")) => Some('<' as u32),
+                (_, _, Some("gt")) => Some('>' as u32),
+                (_, _, Some("quot")) => Some('"' as u32),
+                (_, _, Some("apos")) => Some('\'' as u32),
+                _ => Some('\u{A0}' as u32),
+            };
+            code.and_then(char::from_u32).map(String::from).unwrap_or_else(|| c[0].to_owned())
+        })
+        .into_owned()
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// How the `raw` form of a keyword is chosen among the ways it is written.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RawForm {
+    /// The first occurrence.
+    #[default]
+    First,
+    /// The most frequent form, the earliest one on ties.
+    MostFrequent,
+    /// The most frequent form written in this casing, or the most frequent
+    /// form when there is none.
+    Casing(Casing),
+    /// The first occurrence not at the start of a sentence, where words are
+    /// capitalized whatever they are.
+    NonInitial,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Casing {
+    /// "data science"
+    Lower,
+    /// "Data Science"
+    Title,
+    /// "DATA SCIENCE"
+    Upper,
+}
+impl Casing {
+    fn matches(&self, words: &[String]) -> bool {
+        let letters = words.iter().flat_map(|w| w.chars()).filter(|c| c.is_alphabetic());
+        match self {
+            Casing::Lower => letters.clone().all(|c| !c.is_uppercase()),
+            Casing::Upper => letters.clone().all(|c| !c.is_lowercase()),
+            Casing::Title => words.iter().all(|w| {
+                let mut chars = w.chars().filter(|c| c.is_alphabetic());
+                chars.next().is_none_or(|c| c.is_uppercase()) && chars.all(|c| !c.is_uppercase())
+            }),
+        }
+    }
+}
+
+/// A distinct way a keyword is written in the text, and how often.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SurfaceForm {
+    pub form: String,
+    pub count: usize,
+}
+
+/// The distinct forms among `occurrences`, most frequent first, then in order
+/// of first occurrence.
+pub(crate) fn count(occurrences: &[Vec<String>]) -> Vec<SurfaceForm> {
+    let mut forms = Vec::<SurfaceForm>::new();
+    for words in occurrences {
+        let form = words.join(" ");
+        match forms.iter_mut().find(|f| f.form == form) {
+            Some(existing) => existing.count += 1,
+            None => forms.push(SurfaceForm { form, count: 1 }),
+        }
+    }
+    forms.sort_by_key(|f| std::cmp::Reverse(f.count));
+    forms
+}
+
+/// Picks the occurrence reported as `raw`. `initial` tells which occurrences
+/// start their sentence.
+pub(crate) fn choose<'a>(occurrences: &'a [Vec<String>], initial: &[bool], rule: RawForm) -> &'a [String] {
+    let most_frequent = |matching: &dyn Fn(&[String]) -> bool| {
+        let forms = count(occurrences);
+        let form = forms.iter().find(|f| occurrences.iter().any(|o| o.join(" ") == f.form && matching(o)))?;
+        occurrences.iter().find(|o| o.join(" ") == form.form)
+    };
+    let chosen = match rule {
+        RawForm::First => None,
+        RawForm::MostFrequent => most_frequent(&|_| true),
+        RawForm::Casing(casing) => most_frequent(&|o| casing.matches(o)).or_else(|| most_frequent(&|_| true)),
+        RawForm::NonInitial => occurrences.iter().zip(initial).find(|(_, initial)| !**initial).map(|(o, _)| o),
+    };
+    chosen.unwrap_or(&occurrences[0])
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Deserializer};
+
+use crate::preprocessor::Preprocessor;
+
+/// Maps every alias, and each canonical term itself, to its canonical keyword.
+///
+/// Terms are matched on their lowercase words with inner punctuation dropped,
+/// so "Machine-Learning" and "machine learning" share an entry.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Synonyms {
+    canonical: HashMap<String, String>,
+}
+impl Synonyms {
+    /// Parses one entry per line: the canonical term followed by its aliases,
+    /// comma separated. Blank lines and lines starting with `#` are skipped.
+    pub fn from_csv(csv: &str) -> Synonyms {
+        let mut synonyms = Synonyms::default();
+        for line in csv.lines().map(|l| l.trim()).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            let mut fields = line.split(',').map(|f| f.trim()).filter(|f| !f.is_empty());
+            if let Some(canonical) = fields.next() {
+                synonyms.insert(canonical, fields);
+            }
+        }
+        synonyms
+    }
+
+    pub fn insert<'a>(&mut self, canonical: &str, aliases: impl IntoIterator<Item = &'a str>) {
+        let canonical = Synonyms::match_key(canonical);
+        for alias in aliases {
+            self.canonical.insert(Synonyms::match_key(alias), canonical.to_owned());
+        }
+        self.canonical.insert(canonical.to_owned(), canonical);
+    }
+
+    /// Applies `fold` to every alias and canonical keyword, so they keep
+    /// matching candidates whose words are folded.
+    pub fn folded(self, fold: impl Fn(&str) -> String) -> Synonyms {
+        Synonyms { canonical: self.canonical.into_iter().map(|(alias, canonical)| (fold(&alias), fold(&canonical))).collect() }
+    }
+
+    pub fn extend(&mut self, other: Synonyms) {
+        self.canonical.extend(other.canonical);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.canonical.is_empty()
+    }
+
+    /// Returns the canonical keyword for a candidate, if it has one.
+    pub fn canonical(&self, keyword: &str) -> Option<&str> {
+        self.canonical.get(&Synonyms::match_key(keyword)).map(|c| c.as_str())
+    }
+
+    /// Lowercases `term` and drops punctuation between its words, keeping any
+    /// at the edges so "( CEO" does not match "CEO".
+    fn match_key(term: &str) -> String {
+        let words = Preprocessor::new(term.to_owned(), None, None).split_into_words();
+        let is_word = |w: &String| w.chars().any(|c| c.is_alphanumeric());
+        let first = words.iter().position(is_word).unwrap_or(0);
+        let last = words.iter().rposition(is_word).unwrap_or(0);
+        words
+            .iter()
+            .enumerate()
+            .filter(|(i, w)| *i < first || *i > last || is_word(w))
+            .map(|(_, w)| w.to_lowercase())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+impl<'de> Deserialize<'de> for Synonyms {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Synonyms, D::Error> {
+        let mut synonyms = Synonyms::default();
+        for (canonical, aliases) in HashMap::<String, Vec<String>>::deserialize(deserializer)? {
+            synonyms.insert(&canonical, aliases.iter().map(|a| a.as_str()));
+        }
+        Ok(synonyms)
+    }
+}
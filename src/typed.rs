@@ -0,0 +1,90 @@
+use js_sys::{ArrayBuffer, Error, Float64Array, TypeError, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::{ResultItem, Yake};
+
+/// Separates the keywords in `CompactResults`; words never contain it.
+const SEPARATOR: &str = "\n";
+
+/// Results as flat columns rather than an array of objects: `length` keywords
+/// with their `scores` in a `Float64Array`, and their `keywords` and `raws`
+/// each joined by "\n" into one string, so `keywords.split("\n")[i]` goes with
+/// `scores[i]`.
+#[wasm_bindgen]
+pub struct CompactResults {
+    keywords: String,
+    raws: String,
+    scores: Vec<f64>,
+}
+impl CompactResults {
+    fn new(results: &[ResultItem]) -> CompactResults {
+        CompactResults {
+            keywords: results.iter().map(|r| r.keyword.as_str()).collect::<Vec<&str>>().join(SEPARATOR),
+            raws: results.iter().map(|r| r.raw.as_str()).collect::<Vec<&str>>().join(SEPARATOR),
+            scores: results.iter().map(|r| r.score).collect(),
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl CompactResults {
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.scores.len()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn keywords(&self) -> String {
+        self.keywords.to_owned()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn raws(&self) -> String {
+        self.raws.to_owned()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn scores(&self) -> Float64Array {
+        Float64Array::from(self.scores.as_slice())
+    }
+}
+
+#[wasm_bindgen]
+impl Yake {
+    /// Like `get_n_best`, for UTF-8 text in a `Uint8Array` or `ArrayBuffer`,
+    /// such as the output of `TextEncoder` or a file read. The bytes are
+    /// copied into wasm memory once, without the UTF-16 conversion a string
+    /// goes through. Invalid UTF-8 throws an `Error`.
+    #[wasm_bindgen(unchecked_return_type = "ResultItem[]")]
+    pub fn get_n_best_bytes(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "Uint8Array | ArrayBuffer")] bytes: JsValue,
+        n: Option<usize>,
+    ) -> Result<JsValue, JsValue> {
+        let text = decode(bytes)?;
+        Ok(serde_wasm_bindgen::to_value(&self.extract(text, n))?)
+    }
+
+    /// Like `get_n_best_bytes`, returning `CompactResults` instead of an array
+    /// of objects, which is cheaper to build for many keywords.
+    pub fn get_n_best_compact(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "Uint8Array | ArrayBuffer")] bytes: JsValue,
+        n: Option<usize>,
+    ) -> Result<CompactResults, JsValue> {
+        let text = decode(bytes)?;
+        Ok(CompactResults::new(&self.extract(text, n)))
+    }
+}
+
+fn decode(bytes: JsValue) -> Result<String, JsValue> {
+    let bytes = match bytes.dyn_into::<Uint8Array>() {
+        Ok(array) => array,
+        Err(bytes) => match bytes.dyn_into::<ArrayBuffer>() {
+            Ok(buffer) => Uint8Array::new(&buffer),
+            Err(_) => return Err(TypeError::new("expected a Uint8Array or an ArrayBuffer").into()),
+        },
+    };
+    String::from_utf8(bytes.to_vec()).map_err(|e| Error::new(&format!("input is not valid UTF-8: {}", e.utf8_error())).into())
+}
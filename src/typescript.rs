@@ -0,0 +1,107 @@
+use wasm_bindgen::prelude::*;
+
+/// Shapes of the plain objects passed to and returned by `Yake`, which
+/// wasm-bindgen only knows as `any`. Keep in sync with `Config`,
+/// `CallOptions`, `ResultItem` and `Graph`.
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &'static str = r#"
+/** Options accepted by `Yake.with_options`. Omitted options keep their defaults. */
+export interface YakeOptions {
+    ngram?: number;
+    remove_duplicates?: boolean;
+    stopwords?: string[];
+    /** Extra characters treated as punctuation, besides Unicode punctuation and symbols. */
+    punctuation?: string[];
+    normalization?: Normalization | null;
+    threshold?: number | null;
+    blocklist?: string[];
+    protected?: string[];
+    boosts?: Record<string, number>;
+    min_occurrences?: number;
+    min_sentences?: number;
+    max_frequency?: number | null;
+    synonyms?: Record<string, string[]>;
+    acronyms?: boolean;
+    inner_stopwords?: boolean;
+    /** Part-of-speech patterns such as `"(ADJ)*(NOUN|PROPN)+"`. */
+    pos_patterns?: string[];
+    numbers?: "drop" | "keep" | "attached";
+    hyphens?: "split" | "join" | "both";
+    language?: string;
+    unicode_form?: "nfc" | "nfkc" | null;
+    case_folding?: boolean;
+    strip_diacritics?: boolean;
+    scorer?: Scorer;
+    position?: PositionModel;
+    mode?: "enhanced" | "classic";
+    subgrams?: "off" | "remove_contained" | { additive: number } | { multiplicative: number };
+    raw_form?: "first" | "most_frequent" | "non_initial" | { casing: "lower" | "title" | "upper" };
+    surface_forms?: boolean;
+    stats?: boolean;
+}
+
+export type Normalization = "inverse" | "min_max" | "rank";
+export type Scorer = "yake" | "rake" | "tf_idf" | "text_rank";
+export type PositionModel = "median" | "first_occurrence" | "inverse_position";
+
+/** Options that `Yake.get_n_best_with` overrides for a single call. */
+export interface CallOptions {
+    position?: PositionModel;
+    scorer?: Scorer;
+    normalization?: Normalization;
+    threshold?: number;
+}
+
+/** A keyword. Lower scores are better. */
+export interface ResultItem {
+    /** The keyword as written in the text. */
+    raw: string;
+    /** The normalized form keywords are compared by. */
+    keyword: string;
+    score: number;
+    /** Set when the `normalization` option is. Higher is better. */
+    normalized_score?: number;
+    /** Synonyms of the keyword found in the text. */
+    aliases?: string[];
+    /** The definition of an acronym found in the text. */
+    expansion?: string;
+    /** Set when the `surface_forms` option is. */
+    surface_forms?: SurfaceForm[];
+    /** Set when the `stats` option is. */
+    stats?: KeywordStats;
+}
+
+export interface SurfaceForm {
+    form: string;
+    count: number;
+}
+
+/** Positions count words, punctuation included, from the start of the text. */
+export interface KeywordStats {
+    occurrences: number;
+    sentences: number;
+    first_position: number;
+    last_position: number;
+    ngram_length: number;
+}
+
+/** The term co-occurrence graph returned by `Yake.get_graph`. */
+export interface Graph {
+    nodes: GraphNode[];
+    edges: GraphEdge[];
+}
+
+export interface GraphNode {
+    id: number;
+    term: string;
+    tf: number;
+    weight: number;
+    stopword: boolean;
+}
+
+export interface GraphEdge {
+    source: number;
+    target: number;
+    weight: number;
+}
+"#;
@@ -0,0 +1,35 @@
+use caseless::default_case_fold_str;
+use serde::Deserialize;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Unicode normalization form applied to words before they are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum Form {
+    /// Canonical composition, so "cafe\u{301}" reads as "café".
+    Nfc,
+    /// Compatibility composition, which also folds full-width letters and
+    /// ligatures such as "ﬁ".
+    Nfkc,
+}
+
+/// Composes `word` to `form`, keeping it as is without one.
+pub(crate) fn compose(word: &str, form: Option<Form>) -> String {
+    match form {
+        None => word.to_string(),
+        Some(Form::Nfc) => word.nfc().collect(),
+        Some(Form::Nfkc) => word.nfkc().collect(),
+    }
+}
+
+/// The form `word` is matched by: lowercase, or fully case folded so "ß"
+/// matches "ss", and without diacritics when `strip_diacritics` is set.
+pub(crate) fn fold(word: &str, case_folding: bool, strip_diacritics: bool) -> String {
+    let folded = if case_folding { default_case_fold_str(word) } else { word.to_lowercase() };
+    if strip_diacritics {
+        folded.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect()
+    } else {
+        folded
+    }
+}
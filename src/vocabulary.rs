@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+
+pub(crate) type TermId = u32;
+
+/// Interns lowercase term forms so the rest of the pipeline can work with
+/// dense `u32` ids instead of allocating a fresh `String` per lookup.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct TermDictionary {
+    ids: HashMap<String, TermId>,
+    terms: Vec<String>,
+}
+impl TermDictionary {
+    pub fn new() -> TermDictionary {
+        TermDictionary::default()
+    }
+
+    /// Returns the id of `term`, assigning the next free id on first sight.
+    pub fn intern(&mut self, term: &str) -> TermId {
+        if let Some(id) = self.ids.get(term) {
+            return *id;
+        }
+        let id = self.terms.len() as TermId;
+        self.terms.push(term.to_owned());
+        self.ids.insert(term.to_owned(), id);
+        id
+    }
+
+    pub fn term(&self, id: TermId) -> &str {
+        &self.terms[id as usize]
+    }
+}
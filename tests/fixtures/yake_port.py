@@ -0,0 +1,175 @@
+#!/usr/bin/env python3
+"""A Python port of the reference YAKE package's classic scoring.
+
+It follows yake 0.4.8 (datarepresentation.py and yake.py) but is not that
+package: it was written to cross-check classic mode where the package could
+not be installed. `extract` tokenizes like the package, with segtok;
+run as a script, it scores pre-tokenized sentences read from stdin as
+`{"sentences": [[...], ...], "ngram": 3, "n": 10}`.
+"""
+
+import json
+import math
+import re
+import statistics
+import string
+import sys
+
+EXCLUDE = set(string.punctuation)
+
+
+class Term:
+    def __init__(self, uid, stopword):
+        self.id = uid
+        self.stopword = stopword
+        self.tf = 0.0
+        self.tf_a = 0.0
+        self.tf_n = 0.0
+        self.occurs = {}
+        self.left = {}
+        self.right = {}
+        self.H = 0.0
+
+    def add(self, tag, sentence_id):
+        self.occurs.setdefault(sentence_id, []).append(1)
+        self.tf += 1
+        if tag == "a":
+            self.tf_a += 1
+        if tag == "n":
+            self.tf_n += 1
+
+    def weigh(self, max_tf, avg_tf, std_tf, sentences):
+        wl, wr = sum(self.left.values()), sum(self.right.values())
+        pl = 0 if wl == 0 else len(self.left) / wl
+        pr = 0 if wr == 0 else len(self.right) / wr
+        relatedness = (0.5 + pl * (self.tf / max_tf)) + (0.5 + pr * (self.tf / max_tf))
+        frequency = self.tf / (avg_tf + std_tf)
+        spread = len(self.occurs) / sentences
+        casing = max(self.tf_a, self.tf_n) / (1.0 + math.log(self.tf))
+        position = math.log(math.log(3.0 + statistics.median(list(self.occurs.keys()))))
+        self.H = (position * relatedness) / (casing + ((frequency + spread) / relatedness))
+
+
+def tag_of(word, i):
+    try:
+        float(word.replace(",", ""))
+        return "d"
+    except ValueError:
+        digits = len([c for c in word if c.isdigit()])
+        letters = len([c for c in word if c.isalpha()])
+        if (digits > 0 and letters > 0) or (digits == 0 and letters == 0) or len([c for c in word if c in EXCLUDE]) > 1:
+            return "u"
+        if len(word) == len([c for c in word if c.isupper()]):
+            return "a"
+        if len([c for c in word[0] if c.isupper()]) == 1 and i != 0:
+            return "n"
+    return "p"
+
+
+def ratio(a, b):
+    """1 - edit distance / max(len(a), len(b)), the package's `seqm`."""
+    previous = list(range(len(b) + 1))
+    for i, x in enumerate(a):
+        current = [i + 1] * (len(b) + 1)
+        for j, y in enumerate(b):
+            current[j + 1] = min(previous[j] + (x != y), previous[j + 1] + 1, current[j] + 1)
+        previous = current
+    return 1 - previous[len(b)] / max(len(a), len(b))
+
+
+def score(sentences, stopwords, n=3, window=1, dedup=0.9, top=10):
+    terms, candidates = {}, {}
+
+    def term(word):
+        unique = word.lower()
+        stopword = unique in stopwords
+        if unique.endswith("s") and len(unique) > 3:
+            unique = unique[:-1]
+        if unique in terms:
+            return terms[unique]
+        simple = "".join(c for c in unique if c not in EXCLUDE)
+        terms[unique] = Term(len(terms), stopword or unique in stopwords or len(simple) < 3)
+        return terms[unique]
+
+    def candidate(words):
+        key = " ".join(w[1].lower() for w in words)
+        if key not in candidates:
+            candidates[key] = {"raw": " ".join(w[1] for w in words), "terms": [w[2] for w in words], "tf": 0.0, "tags": set()}
+        candidates[key]["tf"] += 1
+        candidates[key]["tags"].add("".join(w[0] for w in words))
+
+    for sentence_id, sentence in enumerate(sentences):
+        block = []
+        for position, word in enumerate(sentence):
+            if all(c in EXCLUDE for c in word):
+                block = []
+                continue
+            tag, t = tag_of(word, position), term(word)
+            t.add(tag, sentence_id)
+            if tag not in "du":
+                for previous in block[max(0, len(block) - window):]:
+                    if previous[0] not in "du":
+                        previous[2].right[t.id] = previous[2].right.get(t.id, 0) + 1
+                        t.left[previous[2].id] = t.left.get(previous[2].id, 0) + 1
+            words = [(tag, word, t)]
+            candidate(words)
+            for w in reversed(range(max(0, len(block) - (n - 1)), len(block))):
+                words.append(block[w])
+                candidate(words[::-1])
+            block.append((tag, word, t))
+
+    valid = [t.tf for t in terms.values() if not t.stopword]
+    avg, std, max_tf = sum(valid) / len(valid), statistics.pstdev(valid), max(t.tf for t in terms.values())
+    for t in terms.values():
+        t.weigh(max_tf, avg, std, len(sentences))
+
+    ranked = []
+    for key, c in candidates.items():
+        ts = c["terms"]
+        if not any("u" not in tags and "d" not in tags for tags in c["tags"]) or ts[0].stopword or ts[-1].stopword:
+            continue
+        sum_h, prod_h = 0.0, 1.0
+        for i, t in enumerate(ts):
+            if not t.stopword:
+                sum_h += t.H
+                prod_h *= t.H
+                continue
+            p1 = ts[i - 1].right.get(t.id, 0) / ts[i - 1].tf if i > 0 else 0.0
+            p2 = t.right.get(ts[i + 1].id, 0) / ts[i + 1].tf if i < len(ts) - 1 else 0.0
+            prob = p1 * p2
+            prod_h *= 1 + (1 - prob)
+            sum_h -= 1 - prob
+        ranked.append((prod_h / ((sum_h + 1) * c["tf"]), key, c["raw"]))
+    ranked.sort(key=lambda r: r[0])
+
+    selected = []
+    for h, key, raw in ranked:
+        if all(ratio(key, s[1]) <= dedup for s in selected):
+            selected.append((h, key, raw))
+        if len(selected) == top:
+            break
+    return [{"raw": raw, "keyword": key, "score": h} for h, key, raw in selected]
+
+
+def pre_filter(text):
+    capitalized = re.compile(r"^(\s*([A-Z]))")
+    return "".join(("\n\n" if capitalized.match(part) else " ") + part.replace("\t", " ") for part in text.split("\n"))
+
+
+def extract(text, stopwords, n=3, top=10):
+    from segtok.segmenter import split_multi
+    from segtok.tokenizer import split_contractions, web_tokenizer
+
+    sentences = [
+        [w for w in split_contractions(web_tokenizer(s)) if not (w.startswith("'") and len(w) > 1) and len(w) > 0]
+        for s in split_multi(pre_filter(text))
+        if len(s.strip()) > 0
+    ]
+    return score(sentences, stopwords, n=n, top=top)
+
+
+if __name__ == "__main__":
+    from generate_parity import stopwords
+
+    data = json.load(sys.stdin)
+    print(json.dumps(score(data["sentences"], stopwords(), n=data.get("ngram", 3), top=data.get("n", 10)), indent=2))